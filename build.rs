@@ -0,0 +1,14 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+fn main() {
+    println!("cargo::rerun-if-changed=build.rs");
+    // With the `mock` feature the UDMF symbols are defined by the crate itself. `udmf-sys` still
+    // asks the linker for `libudmf`, so provide an empty archive that satisfies the lookup.
+    if env::var_os("CARGO_FEATURE_MOCK").is_some() {
+        let out_dir = PathBuf::from(env::var_os("OUT_DIR").unwrap());
+        fs::write(out_dir.join("libudmf.a"), b"!<arch>\n").unwrap();
+        println!("cargo::rustc-link-search=native={}", out_dir.display());
+    }
+}
@@ -0,0 +1,102 @@
+use crate::error::Result;
+use crate::types::UniformDataType;
+use crate::uds::{
+    AppItem, ArrayBuffer, ContentForm, FileUri, Html, Hyperlink, PixelMap, PlainText,
+};
+use crate::unified_data::UnifiedRecord;
+use std::ffi::c_void;
+
+/// A single typed entry of a [`UnifiedRecord`].
+pub enum Entry {
+    PlainText(PlainText),
+    Html(Html),
+    Hyperlink(Hyperlink),
+    AppItem(AppItem),
+    FileUri(FileUri),
+    PixelMap(PixelMap),
+    ContentForm(ContentForm),
+    /// The raw bytes of a type without a dedicated UDS.
+    General(UniformDataType, Vec<u8>),
+}
+
+impl Entry {
+    /// Get the type this entry is stored under in a record.
+    pub fn type_id(&self) -> UniformDataType {
+        match self {
+            Entry::PlainText(_) => UniformDataType::PlainText,
+            Entry::Html(_) => UniformDataType::Html,
+            Entry::Hyperlink(_) => UniformDataType::Hyperlink,
+            Entry::AppItem(_) => UniformDataType::OpenHarmonyAppItem,
+            Entry::FileUri(_) => UniformDataType::GeneralFileUri,
+            Entry::PixelMap(_) => UniformDataType::OpenHarmonyPixelMap,
+            Entry::ContentForm(_) => UniformDataType::GeneralContentForm,
+            Entry::General(type_id, _) => type_id.clone(),
+        }
+    }
+
+    /// Convert the entry into a raw UDS object, passing ownership to the caller.
+    ///
+    /// General entries are converted into an `OH_UdsArrayBuffer`.
+    pub(crate) fn into_raw_uds(self) -> Result<*mut c_void> {
+        let ptr = match self {
+            Entry::PlainText(uds) => uds.into_raw().cast(),
+            Entry::Html(uds) => uds.into_raw().cast(),
+            Entry::Hyperlink(uds) => uds.into_raw().cast(),
+            Entry::AppItem(uds) => uds.into_raw().cast(),
+            Entry::FileUri(uds) => uds.into_raw().cast(),
+            Entry::PixelMap(uds) => uds.into_raw().cast(),
+            Entry::ContentForm(uds) => uds.into_raw().cast(),
+            Entry::General(_, data) => {
+                let mut buffer = ArrayBuffer::new()?;
+                buffer.set_data(&data)?;
+                buffer.into_raw().cast()
+            }
+        };
+        Ok(ptr)
+    }
+}
+
+impl UnifiedRecord {
+    /// Add `entry` to this record.
+    pub fn add_entry(&mut self, entry: &Entry) -> Result<()> {
+        match entry {
+            Entry::PlainText(uds) => self.add_plain_text(uds),
+            Entry::Html(uds) => self.add_html(uds),
+            Entry::Hyperlink(uds) => self.add_hyperlink(uds),
+            Entry::AppItem(uds) => self.add_app_item(uds),
+            Entry::FileUri(uds) => self.add_file_uri(uds),
+            Entry::PixelMap(uds) => self.add_pixel_map(uds),
+            Entry::ContentForm(uds) => self.add_content_form(uds),
+            Entry::General(type_id, data) => self.add_general_entry(type_id, data),
+        }
+    }
+
+    /// Get the entry stored under `type_id`.
+    ///
+    /// Types without a dedicated UDS are returned as [`Entry::General`], which includes entries
+    /// added with [`add_array_buffer`](UnifiedRecord::add_array_buffer).
+    pub fn get_entry(&self, type_id: &UniformDataType) -> Result<Entry> {
+        let entry = match type_id {
+            UniformDataType::PlainText => Entry::PlainText(self.get_plain_text()?),
+            UniformDataType::Html => Entry::Html(self.get_html()?),
+            UniformDataType::Hyperlink => Entry::Hyperlink(self.get_hyperlink()?),
+            UniformDataType::OpenHarmonyAppItem => Entry::AppItem(self.get_app_item()?),
+            UniformDataType::GeneralFileUri => Entry::FileUri(self.get_file_uri()?),
+            UniformDataType::OpenHarmonyPixelMap => Entry::PixelMap(self.get_pixel_map()?),
+            UniformDataType::GeneralContentForm => Entry::ContentForm(self.get_content_form()?),
+            _ => Entry::General(type_id.clone(), self.get_general_entry(type_id)?),
+        };
+        Ok(entry)
+    }
+
+    /// Iterate over all entries of this record together with their type.
+    ///
+    /// An entry that can not be read is yielded as an error, the remaining entries are still
+    /// visited.
+    pub fn entries(&self) -> impl Iterator<Item = Result<(UniformDataType, Entry)>> + '_ {
+        self.get_types().into_iter().map(|type_id| {
+            let entry = self.get_entry(&type_id)?;
+            Ok((type_id, entry))
+        })
+    }
+}
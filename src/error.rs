@@ -1,38 +1,148 @@
+use crate::types::UniformDataType;
+use std::fmt;
 use udmf_sys::Udmf_ErrCode;
 
-#[derive(Debug, thiserror::Error)]
-pub enum UdmfError {
-    #[error("Internal error: {0}")]
-    InternalError(u32),
-    #[error("Invalid parameter")]
-    InvalidParam,
-    #[error("Unknown error code: {0}")]
-    Unknown(u32),
+/// The UDMF function that failed and the type it was called with, if any.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorContext {
+    /// The name of the C function, e.g. `OH_UdmfRecord_GetPlainText`, or of the conversion that
+    /// failed before UDMF was called.
+    pub operation: &'static str,
+    pub type_id: Option<UniformDataType>,
 }
 
-pub type Result<T> = std::result::Result<T, UdmfError>;
+impl ErrorContext {
+    pub(crate) fn new(operation: &'static str) -> Self {
+        Self {
+            operation,
+            type_id: None,
+        }
+    }
+}
 
-impl From<i32> for UdmfError {
-    fn from(code: i32) -> Self {
-        Self::from(code as u32)
+impl fmt::Display for ErrorContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.type_id {
+            Some(type_id) => write!(f, "{} ({type_id})", self.operation),
+            None => write!(f, "{}", self.operation),
+        }
     }
 }
 
-impl From<u32> for UdmfError {
-    fn from(code: u32) -> Self {
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum UdmfError {
+    /// UDMF reported an internal error (`ERR`).
+    #[error("{context}: internal error")]
+    InternalError { context: ErrorContext },
+    /// A parameter was rejected (`E_INVALID_PARAM`), either by UDMF or before calling it, e.g.
+    /// because a string contains a NUL byte.
+    #[error("{context}: invalid parameter")]
+    InvalidParam { context: ErrorContext },
+    /// The record or data has no entry of the requested type. UDMF reports this as `ERR`.
+    #[error("{context}: type not present")]
+    TypeNotFound { context: ErrorContext },
+    /// A UDMF constructor returned null.
+    #[error("{operation}: allocation failed")]
+    NullPointer { operation: &'static str },
+    /// UDMF returned an error code that is not part of `Udmf_ErrCode`.
+    #[error("{context}: unknown error code {code}")]
+    Unknown { code: u32, context: ErrorContext },
+    /// The image kit returned an `Image_ErrorCode` other than `IMAGE_SUCCESS`.
+    #[error("{operation}: image error {code}")]
+    Image { code: i32, operation: &'static str },
+    #[cfg(feature = "api-15")]
+    #[error("Data retrieval failed: {0:?}")]
+    RetrievalFailed(crate::get_data::ListenerStatus),
+}
+
+pub type Result<T> = std::result::Result<T, UdmfError>;
+
+impl UdmfError {
+    /// Create the error for the status `code` returned by `operation`, or `None` for `E_OK`.
+    pub fn from_code(code: u32, operation: &'static str) -> Option<Self> {
+        let context = ErrorContext::new(operation);
         match Udmf_ErrCode(code) {
-            Udmf_ErrCode::E_OK => panic!("E_OK is not an error"),
-            Udmf_ErrCode::ERR => UdmfError::InternalError(code),
-            Udmf_ErrCode::E_INVALID_PARAM => UdmfError::InvalidParam,
-            _ => UdmfError::Unknown(code),
+            Udmf_ErrCode::E_OK => None,
+            Udmf_ErrCode::ERR => Some(Self::InternalError { context }),
+            Udmf_ErrCode::E_INVALID_PARAM => Some(Self::InvalidParam { context }),
+            _ => Some(Self::Unknown { code, context }),
+        }
+    }
+
+    /// Get the UDMF status code corresponding to this error.
+    ///
+    /// Errors that do not originate from a status code map to `ERR`.
+    pub fn code(&self) -> Udmf_ErrCode {
+        match self {
+            Self::InvalidParam { .. } => Udmf_ErrCode::E_INVALID_PARAM,
+            Self::Unknown { code, .. } => Udmf_ErrCode(*code),
+            _ => Udmf_ErrCode::ERR,
+        }
+    }
+
+    /// Get the function and type the error occurred in, if known.
+    pub fn context(&self) -> Option<&ErrorContext> {
+        match self {
+            Self::InternalError { context }
+            | Self::InvalidParam { context }
+            | Self::TypeNotFound { context }
+            | Self::Unknown { context, .. } => Some(context),
+            _ => None,
+        }
+    }
+
+    fn context_mut(&mut self) -> Option<&mut ErrorContext> {
+        match self {
+            Self::InternalError { context }
+            | Self::InvalidParam { context }
+            | Self::TypeNotFound { context }
+            | Self::Unknown { context, .. } => Some(context),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn invalid_param(operation: &'static str) -> Self {
+        Self::InvalidParam {
+            context: ErrorContext::new(operation),
+        }
+    }
+
+    pub(crate) fn type_not_found(operation: &'static str, type_id: &UniformDataType) -> Self {
+        Self::TypeNotFound {
+            context: ErrorContext::new(operation),
+        }
+        .with_type_id(type_id)
+    }
+
+    pub(crate) fn null_pointer(operation: &'static str) -> Self {
+        Self::NullPointer { operation }
+    }
+
+    /// Record the type the failed operation was called with.
+    pub(crate) fn with_type_id(mut self, type_id: &UniformDataType) -> Self {
+        if let Some(context) = self.context_mut() {
+            context.type_id = Some(type_id.clone());
+        }
+        self
+    }
+
+    /// Report an internal error as [`TypeNotFound`](Self::TypeNotFound) if `present` is false.
+    ///
+    /// UDMF does not distinguish a missing type from other failures, so callers check
+    /// whether the type is present after the fact.
+    pub(crate) fn or_type_not_found(self, present: impl FnOnce() -> bool) -> Self {
+        match self {
+            Self::InternalError { context } if !present() => Self::TypeNotFound { context },
+            other => other,
         }
     }
 }
 
-pub(crate) fn to_result(code: i32) -> Result<()> {
-    if code == Udmf_ErrCode::E_OK.0 as i32 {
-        Ok(())
-    } else {
-        Err(UdmfError::from(code))
+/// Convert the status `code` returned by `operation` into a `Result`.
+pub(crate) fn to_result(code: i32, operation: &'static str) -> Result<()> {
+    match UdmfError::from_code(code as u32, operation) {
+        None => Ok(()),
+        Some(e) => Err(e),
     }
 }
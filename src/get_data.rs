@@ -0,0 +1,431 @@
+use crate::error::{Result, UdmfError};
+use crate::unified_data::UnifiedData;
+use ohos_sys_opaque_types::*;
+use std::ffi::{CStr, CString};
+use std::future::Future;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::task::{Context, Poll, Waker};
+use udmf_sys::Udmf_ListenerStatus;
+use udmf_sys::data_management_framework::*;
+
+/// What to do when a file with the same name already exists at the destination URI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum FileConflictOptions {
+    #[default]
+    Overwrite,
+    Skip,
+}
+
+impl From<FileConflictOptions> for Udmf_FileConflictOptions {
+    fn from(options: FileConflictOptions) -> Self {
+        match options {
+            FileConflictOptions::Overwrite => Udmf_FileConflictOptions::UDMF_OVERWRITE,
+            FileConflictOptions::Skip => Udmf_FileConflictOptions::UDMF_SKIP,
+        }
+    }
+}
+
+/// Whether the system shows its default progress indicator while retrieving data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ProgressIndicator {
+    #[default]
+    None,
+    Default,
+}
+
+impl From<ProgressIndicator> for Udmf_ProgressIndicator {
+    fn from(indicator: ProgressIndicator) -> Self {
+        match indicator {
+            ProgressIndicator::None => Udmf_ProgressIndicator::UDMF_NONE,
+            ProgressIndicator::Default => Udmf_ProgressIndicator::UDMF_DEFAULT,
+        }
+    }
+}
+
+/// The status reported to a progress listener.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ListenerStatus {
+    Finished,
+    Processing,
+    Canceled,
+    InnerError,
+    InvalidParameters,
+    DataNotFound,
+    SyncFailed,
+    CopyFileFailed,
+    Unknown(u32),
+}
+
+impl ListenerStatus {
+    /// Returns `true` if no further progress will be reported.
+    pub fn is_terminal(&self) -> bool {
+        *self != ListenerStatus::Processing
+    }
+}
+
+impl From<u32> for ListenerStatus {
+    fn from(status: u32) -> Self {
+        match Udmf_ListenerStatus(status) {
+            Udmf_ListenerStatus::UDMF_FINISHED => Self::Finished,
+            Udmf_ListenerStatus::UDMF_PROCESSING => Self::Processing,
+            Udmf_ListenerStatus::UDMF_CANCELED => Self::Canceled,
+            Udmf_ListenerStatus::UDMF_INNER_ERROR => Self::InnerError,
+            Udmf_ListenerStatus::UDMF_INVALID_PARAMETERS => Self::InvalidParameters,
+            Udmf_ListenerStatus::UDMF_DATA_NOT_FOUND => Self::DataNotFound,
+            Udmf_ListenerStatus::UDMF_SYNC_FAILED => Self::SyncFailed,
+            Udmf_ListenerStatus::UDMF_COPY_FILE_FAILED => Self::CopyFileFailed,
+            _ => Self::Unknown(status),
+        }
+    }
+}
+
+impl From<ListenerStatus> for Udmf_ListenerStatus {
+    fn from(status: ListenerStatus) -> Self {
+        match status {
+            ListenerStatus::Finished => Udmf_ListenerStatus::UDMF_FINISHED,
+            ListenerStatus::Processing => Udmf_ListenerStatus::UDMF_PROCESSING,
+            ListenerStatus::Canceled => Udmf_ListenerStatus::UDMF_CANCELED,
+            ListenerStatus::InnerError => Udmf_ListenerStatus::UDMF_INNER_ERROR,
+            ListenerStatus::InvalidParameters => Udmf_ListenerStatus::UDMF_INVALID_PARAMETERS,
+            ListenerStatus::DataNotFound => Udmf_ListenerStatus::UDMF_DATA_NOT_FOUND,
+            ListenerStatus::SyncFailed => Udmf_ListenerStatus::UDMF_SYNC_FAILED,
+            ListenerStatus::CopyFileFailed => Udmf_ListenerStatus::UDMF_COPY_FILE_FAILED,
+            ListenerStatus::Unknown(status) => Udmf_ListenerStatus(status),
+        }
+    }
+}
+
+/// A progress update of a data retrieval.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ProgressInfo {
+    /// The progress in percent.
+    pub progress: i32,
+    pub status: ListenerStatus,
+}
+
+type Listener = Box<dyn FnMut(ProgressInfo, Option<&UnifiedData>) + Send>;
+
+/// The update passed to a listener whose request was replaced or dropped.
+const CANCELED: ProgressInfo = ProgressInfo {
+    progress: 0,
+    status: ListenerStatus::Canceled,
+};
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Call `listener`, which must not unwind into UDMF.
+fn call(listener: &mut Listener, info: ProgressInfo, data: Option<&UnifiedData>) {
+    let _ = catch_unwind(AssertUnwindSafe(|| listener(info, data)));
+}
+
+enum ListenerState {
+    Idle(Listener),
+    /// The listener is being called. `canceled` records a cancellation during the call.
+    Running {
+        canceled: bool,
+    },
+    /// A terminal update was delivered, or the request was canceled.
+    Done,
+}
+
+/// The listener of one request, shared by the process-wide slot and the [`GetDataParams`] that
+/// installed it.
+#[derive(Clone)]
+struct InstalledListener {
+    generation: u64,
+    state: Arc<Mutex<ListenerState>>,
+}
+
+impl InstalledListener {
+    fn new(listener: Listener) -> Self {
+        static NEXT_GENERATION: AtomicU64 = AtomicU64::new(0);
+        Self {
+            generation: NEXT_GENERATION.fetch_add(1, Ordering::Relaxed),
+            state: Arc::new(Mutex::new(ListenerState::Idle(listener))),
+        }
+    }
+
+    /// Deliver an update. The listener is taken out of its state while it runs, so it may
+    /// build a new request, which cancels this one.
+    fn notify(&self, info: ProgressInfo, data: Option<&UnifiedData>) {
+        let mut listener = {
+            let mut state = lock(&self.state);
+            match std::mem::replace(&mut *state, ListenerState::Running { canceled: false }) {
+                ListenerState::Idle(listener) => listener,
+                other => {
+                    *state = other;
+                    return;
+                }
+            }
+        };
+        call(&mut listener, info, data);
+        let mut state = lock(&self.state);
+        let canceled = matches!(*state, ListenerState::Running { canceled: true });
+        if info.status.is_terminal() || canceled {
+            *state = ListenerState::Done;
+            drop(state);
+            if !info.status.is_terminal() {
+                call(&mut listener, CANCELED, None);
+            }
+        } else {
+            *state = ListenerState::Idle(listener);
+        }
+    }
+
+    /// Report [`ListenerStatus::Canceled`] unless a terminal update was already delivered.
+    fn cancel(&self) {
+        let mut state = lock(&self.state);
+        match std::mem::replace(&mut *state, ListenerState::Done) {
+            ListenerState::Idle(mut listener) => {
+                drop(state);
+                call(&mut listener, CANCELED, None);
+            }
+            ListenerState::Running { .. } => *state = ListenerState::Running { canceled: true },
+            ListenerState::Done => {}
+        }
+    }
+}
+
+/// The process-wide progress listener. UDMF does not pass a context to the listener.
+static LISTENER: Mutex<Option<InstalledListener>> = Mutex::new(None);
+
+unsafe extern "C" fn progress_trampoline(
+    progress_info: *mut OH_Udmf_ProgressInfo,
+    data: *mut OH_UdmfData,
+) {
+    if progress_info.is_null() {
+        return;
+    }
+    // SAFETY: progress_info is a valid pointer provided by UDMF for the duration of the call.
+    let info = unsafe {
+        ProgressInfo {
+            progress: OH_UdmfProgressInfo_GetProgress(progress_info),
+            status: ListenerStatus::from(OH_UdmfProgressInfo_GetStatus(progress_info) as u32),
+        }
+    };
+    let Some(listener) = lock(&LISTENER).clone() else {
+        return;
+    };
+    let data = if data.is_null() {
+        None
+    } else {
+        // SAFETY: data is a valid pointer owned by UDMF for the duration of the call.
+        Some(unsafe { UnifiedData::from_ptr(data, false) })
+    };
+    listener.notify(info, data.as_ref());
+    if info.status.is_terminal() {
+        let mut slot = lock(&LISTENER);
+        if slot.as_ref().map(|l| l.generation) == Some(listener.generation) {
+            *slot = None;
+        }
+    }
+}
+
+/// Builder for the parameters of an asynchronous data retrieval.
+///
+/// The parameters are consumed by the drag and drop APIs of ArkUI, e.g.
+/// `OH_ArkUI_DragEvent_StartDataLoading`, which copy files to the destination URI in the
+/// background and report progress to the listener.
+///
+/// There is only one process-wide progress listener, so building a new request replaces the
+/// listener of any previous request, which then receives [`ListenerStatus::Canceled`].
+pub struct GetDataRequest {
+    dest_uri: Option<CString>,
+    file_conflict_options: FileConflictOptions,
+    progress_indicator: ProgressIndicator,
+    listener: Option<Listener>,
+}
+
+impl GetDataRequest {
+    pub fn new() -> Self {
+        Self {
+            dest_uri: None,
+            file_conflict_options: FileConflictOptions::default(),
+            progress_indicator: ProgressIndicator::default(),
+            listener: None,
+        }
+    }
+
+    /// Set the URI of the directory that files are copied to.
+    pub fn dest_uri(self, uri: &str) -> Result<Self> {
+        let c_uri = CString::new(uri)
+            .map_err(|_| UdmfError::invalid_param("OH_UdmfGetDataParams_SetDestUri"))?;
+        Ok(self.dest_uri_cstr(&c_uri))
+    }
+
+    pub fn dest_uri_cstr(mut self, uri: &CStr) -> Self {
+        self.dest_uri = Some(uri.to_owned());
+        self
+    }
+
+    pub fn file_conflict_options(mut self, options: FileConflictOptions) -> Self {
+        self.file_conflict_options = options;
+        self
+    }
+
+    pub fn progress_indicator(mut self, indicator: ProgressIndicator) -> Self {
+        self.progress_indicator = indicator;
+        self
+    }
+
+    /// Set a listener that is called with progress updates and, once finished, with the data.
+    pub fn on_progress<F>(mut self, listener: F) -> Self
+    where
+        F: FnMut(ProgressInfo, Option<&UnifiedData>) + Send + 'static,
+    {
+        self.listener = Some(Box::new(listener));
+        self
+    }
+
+    /// Create the parameters and install the progress listener.
+    pub fn build(self) -> Result<GetDataParams> {
+        // SAFETY: OH_UdmfGetDataParams_Create creates a new instance of OH_UdmfGetDataParams.
+        let inner = unsafe { OH_UdmfGetDataParams_Create() };
+        if inner.is_null() {
+            return Err(UdmfError::null_pointer("OH_UdmfGetDataParams_Create"));
+        }
+        // SAFETY: inner is a valid pointer and dest_uri is a valid C string.
+        unsafe {
+            if let Some(dest_uri) = &self.dest_uri {
+                OH_UdmfGetDataParams_SetDestUri(inner, dest_uri.as_ptr());
+            }
+            OH_UdmfGetDataParams_SetFileConflictOptions(inner, self.file_conflict_options.into());
+            OH_UdmfGetDataParams_SetProgressIndicator(inner, self.progress_indicator.into());
+        }
+        let listener = self.listener.map(InstalledListener::new);
+        if let Some(listener) = &listener {
+            let replaced = lock(&LISTENER).replace(listener.clone());
+            if let Some(replaced) = replaced {
+                replaced.cancel();
+            }
+            // SAFETY: inner is a valid pointer and progress_trampoline matches the listener signature.
+            unsafe {
+                OH_UdmfGetDataParams_SetDataProgressListener(inner, Some(progress_trampoline))
+            };
+        }
+        Ok(GetDataParams {
+            inner,
+            _dest_uri: self.dest_uri,
+            listener,
+        })
+    }
+
+    /// Create the parameters together with a future that resolves to the retrieved data.
+    ///
+    /// A listener set with [`on_progress`](Self::on_progress) still receives all updates.
+    pub fn build_future(mut self) -> Result<(GetDataParams, GetDataFuture)> {
+        let state = Arc::new(Mutex::new(FutureState::default()));
+        let listener_state = Arc::clone(&state);
+        let mut user_listener = self.listener.take();
+        self = self.on_progress(move |info, data| {
+            if let Some(listener) = user_listener.as_mut() {
+                listener(info, data);
+            }
+            if info.status.is_terminal() {
+                let result = match (info.status, data) {
+                    (ListenerStatus::Finished, Some(data)) => data.copy(),
+                    (ListenerStatus::Finished, None) => UnifiedData::new(),
+                    (status, _) => Err(UdmfError::RetrievalFailed(status)),
+                };
+                let mut state = lock(&listener_state);
+                state.result = Some(result.map(SendData));
+                if let Some(waker) = state.waker.take() {
+                    waker.wake();
+                }
+            }
+        });
+        Ok((self.build()?, GetDataFuture { state }))
+    }
+}
+
+impl Default for GetDataRequest {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Parameters of an asynchronous data retrieval.
+///
+/// Pass [`as_ptr`](Self::as_ptr) to the API that performs the retrieval and keep the parameters
+/// alive until it has finished. Dropping them earlier uninstalls the progress listener, which
+/// then receives [`ListenerStatus::Canceled`].
+pub struct GetDataParams {
+    inner: *mut OH_UdmfGetDataParams,
+    _dest_uri: Option<CString>,
+    listener: Option<InstalledListener>,
+}
+
+impl GetDataParams {
+    /// Returns the underlying raw pointer to `OH_UdmfGetDataParams`.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the pointer is only used while the `GetDataParams`
+    /// object is alive.
+    pub unsafe fn as_ptr(&self) -> *mut OH_UdmfGetDataParams {
+        self.inner
+    }
+
+    /// Report a progress update to the listener of these parameters, as ArkUI would during a
+    /// retrieval. Only available with the `mock` feature.
+    #[cfg(feature = "mock")]
+    pub fn mock_progress(&self, progress: i32, status: ListenerStatus, data: Option<&UnifiedData>) {
+        // SAFETY: the pointer is only used during the call, while data is borrowed.
+        let data = data.map_or(std::ptr::null_mut(), |data| unsafe { data.as_ptr() });
+        crate::mock::get_data::report_progress(self.inner, progress, status.into(), data);
+    }
+}
+
+impl Drop for GetDataParams {
+    fn drop(&mut self) {
+        if let Some(listener) = &self.listener {
+            let mut slot = lock(&LISTENER);
+            if slot.as_ref().map(|l| l.generation) == Some(listener.generation) {
+                *slot = None;
+            }
+            drop(slot);
+            listener.cancel();
+        }
+        if !self.inner.is_null() {
+            // SAFETY: we own the inner pointer and it is valid.
+            unsafe { OH_UdmfGetDataParams_Destroy(self.inner) };
+        }
+    }
+}
+
+/// The retrieved data is a fresh copy that is handed from the listener to the future exactly once.
+struct SendData(UnifiedData);
+
+// SAFETY: the wrapped data is exclusively owned and only accessed by one thread at a time.
+unsafe impl Send for SendData {}
+
+#[derive(Default)]
+struct FutureState {
+    result: Option<Result<SendData>>,
+    waker: Option<Waker>,
+}
+
+/// A future that resolves once the data retrieval has finished.
+pub struct GetDataFuture {
+    state: Arc<Mutex<FutureState>>,
+}
+
+impl Future for GetDataFuture {
+    type Output = Result<UnifiedData>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = lock(&self.state);
+        match state.result.take() {
+            Some(result) => Poll::Ready(result.map(|data| data.0)),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
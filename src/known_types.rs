@@ -0,0 +1,399 @@
+//! Static metadata of the uniform data types known to this crate.
+//!
+//! The table includes a copy of the standard UTD hierarchy with the MIME types and filename
+//! extensions of every type, so relations between known types can be answered without the
+//! platform type descriptor service. Custom types are unknown to the table; use
+//! [`TypeDescriptor`](crate::TypeDescriptor) for them.
+
+use crate::types::UniformDataType;
+use std::collections::{HashSet, VecDeque};
+use std::ffi::CStr;
+use udmf_sys::meta::*;
+
+/// Broad category of a uniform data type, e.g. for grouping types in a picker.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TypeCategory {
+    /// Abstract base types, files, directories and other types without a more specific category.
+    General,
+    Text,
+    Image,
+    Audio,
+    Video,
+    Archive,
+    Document,
+    Font,
+    /// Types specific to OpenHarmony, e.g. app items and pixel maps.
+    OpenHarmony,
+}
+
+/// Static metadata of a known [`UniformDataType`].
+#[derive(Debug)]
+pub struct TypeMetadata {
+    pub type_id: UniformDataType,
+    /// The canonical type id, e.g. `general.plain-text`.
+    pub id: &'static str,
+    pub category: TypeCategory,
+    /// The OpenHarmony API level that introduced the type.
+    pub api_level: u32,
+    pub description: &'static CStr,
+    /// The direct parents of the type in the standard UTD hierarchy.
+    pub belonging_to_types: &'static [UniformDataType],
+    /// Filename extensions including the leading dot, e.g. `.txt`.
+    pub filename_extensions: &'static [&'static CStr],
+    pub mime_types: &'static [&'static CStr],
+}
+
+const fn ascii_str(s: &'static CStr) -> &'static str {
+    match s.to_str() {
+        Ok(s) => s,
+        Err(_) => panic!("the type table is ASCII"),
+    }
+}
+
+macro_rules! known_types {
+    ($(
+        $variant:ident => $id:ident, $category:ident, $api_level:literal,
+        [$($parent:ident),*], [$($extension:literal),*], [$($mime_type:literal),*],
+        $description:literal;
+    )+) => {
+        /// All variants of [`UniformDataType`] except [`Other`](UniformDataType::Other), in
+        /// declaration order.
+        pub static KNOWN_TYPES: &[TypeMetadata] = &[$(
+            TypeMetadata {
+                type_id: UniformDataType::$variant,
+                id: ascii_str($id),
+                category: TypeCategory::$category,
+                api_level: $api_level,
+                description: $description,
+                belonging_to_types: &[$(UniformDataType::$parent),*],
+                filename_extensions: &[$($extension),*],
+                mime_types: &[$($mime_type),*],
+            },
+        )+];
+
+        /// The index of the entry of `type_id` in [`KNOWN_TYPES`].
+        ///
+        /// The match has no wildcard, so a variant without an entry fails to compile.
+        const fn table_index(type_id: &UniformDataType) -> Option<usize> {
+            enum Index {
+                $($variant,)+
+            }
+            match type_id {
+                $(UniformDataType::$variant => Some(Index::$variant as usize),)+
+                UniformDataType::Other(_) => None,
+            }
+        }
+    };
+}
+
+known_types! {
+    Entity => UDMF_META_ENTITY, General, 12, [], [], [], c"Base type for physical hierarchy.";
+    Object => UDMF_META_OBJECT, General, 12, [], [], [c"*/*"], c"Base type for logical hierarchy.";
+    CompositeObject => UDMF_META_COMPOSITE_OBJECT, General, 12, [Object], [], [],
+        c"Base type for mixed object.";
+    Text => UDMF_META_TEXT, Text, 12, [Object], [], [c"text/*"], c"Base type for all text.";
+    PlainText => UDMF_META_PLAIN_TEXT, Text, 12, [Text], [c".txt", c".text"], [c"text/plain"],
+        c"Text of unspecified encoding, with no markup.";
+    Html => UDMF_META_HTML, Text, 12, [Text], [c".html", c".htm"], [c"text/html"], c"HTML text.";
+    Hyperlink => UDMF_META_HYPERLINK, Text, 12, [Text], [], [], c"Hyperlink.";
+    Xml => UDMF_META_XML, Text, 12, [Text], [c".xml"], [c"text/xml", c"application/xml"],
+        c"XML text.";
+    SourceCode => UDMF_META_SOURCE_CODE, Text, 12, [Text], [], [], c"Generic source code.";
+    Script => UDMF_META_SCRIPT, Text, 12, [SourceCode], [], [],
+        c"Base type for scripting language source code.";
+    ShellScript => UDMF_META_SHELL_SCRIPT, Text, 12, [Script], [c".sh", c".command"],
+        [c"text/x-shellscript"], c"Shell script.";
+    CshScript => UDMF_META_CSH_SCRIPT, Text, 12, [ShellScript], [c".csh"], [c"text/x-csh"],
+        c"C-shell script.";
+    PerlScript => UDMF_META_PERL_SCRIPT, Text, 12, [ShellScript], [c".pl", c".pm"],
+        [c"text/x-perl-script"], c"Perl script.";
+    PhpScript => UDMF_META_PHP_SCRIPT, Text, 12, [ShellScript],
+        [c".php", c".php3", c".php4", c".ph3", c".ph4", c".phtml"],
+        [c"text/x-php-script", c"text/php", c"application/php"], c"PHP script.";
+    PythonScript => UDMF_META_PYTHON_SCRIPT, Text, 12, [ShellScript], [c".py"],
+        [c"text/x-python-script"], c"Python script.";
+    RubyScript => UDMF_META_RUBY_SCRIPT, Text, 12, [ShellScript], [c".rb", c".rbw"],
+        [c"text/ruby-script"], c"Ruby script.";
+    TypeScript => UDMF_META_TYPE_SCRIPT, Text, 12, [SourceCode], [c".ts"], [],
+        c"TypeScript source code.";
+    JavaScript => UDMF_META_JAVA_SCRIPT, Text, 12, [SourceCode],
+        [c".js", c".jscript", c".javascript"], [c"text/javascript"], c"JavaScript source code.";
+    CHeader => UDMF_META_C_HEADER, Text, 12, [SourceCode], [c".h"], [c"text/x-chdr"],
+        c"C header file.";
+    CSource => UDMF_META_C_SOURCE, Text, 12, [SourceCode], [c".c"], [c"text/x-csrc"],
+        c"C source code.";
+    CPlusPlusHeader => UDMF_META_C_PLUS_PLUS_HEADER, Text, 12, [SourceCode],
+        [c".hpp", c".h++", c".hxx", c".hh"], [c"text/x-c++hdr"], c"C++ header file.";
+    CPlusPlusSource => UDMF_META_C_PLUS_PLUS_SOURCE, Text, 12, [SourceCode],
+        [c".cp", c".cpp", c".c++", c".cc", c".cxx"], [c"text/x-c++src"], c"C++ source code.";
+    JavaSource => UDMF_META_JAVA_SOURCE, Text, 12, [SourceCode], [c".java", c".jav"],
+        [c"text/x-java"], c"Java source code.";
+    Ebook => UDMF_META_EBOOK, Document, 12, [CompositeObject], [], [], c"Base type for ebook.";
+    Epub => UDMF_META_EPUB, Document, 12, [Ebook], [c".epub"], [c"application/epub+zip"],
+        c"Electronic publication (EPUB) format.";
+    Azw => UDMF_META_AZW, Document, 12, [Ebook], [c".azw"], [c"application/vnd.amazon.ebook"],
+        c"AZW ebook.";
+    Azw3 => UDMF_META_AZW3, Document, 12, [Ebook], [c".azw3"],
+        [c"application/vnd.amazon.mobi8-ebook", c"application/x-mobi8-ebook"], c"AZW3 ebook.";
+    Kfx => UDMF_META_KFX, Document, 12, [Ebook], [c".kfx"], [], c"KFX ebook.";
+    Mobi => UDMF_META_MOBI, Document, 12, [Ebook], [c".mobi"], [c"application/x-mobipocket-ebook"],
+        c"MOBI ebook.";
+    Media => UDMF_META_MEDIA, General, 12, [Object], [], [], c"Base type for media.";
+    Image => UDMF_META_IMAGE, Image, 12, [Media], [], [c"image/*"], c"Base type for images.";
+    Jpeg => UDMF_META_JPEG, Image, 12, [Image], [c".jpg", c".jpeg", c".jpe"], [c"image/jpeg"],
+        c"JPEG image.";
+    Png => UDMF_META_PNG, Image, 12, [Image], [c".png"], [c"image/png"], c"PNG image.";
+    RawImage => UDMF_META_RAW_IMAGE, Image, 12, [Image], [], [],
+        c"Base type for digital camera raw images.";
+    Tiff => UDMF_META_TIFF, Image, 12, [Image], [c".tif", c".tiff"], [c"image/tiff"],
+        c"TIFF image.";
+    Bmp => UDMF_META_BMP, Image, 12, [Image], [c".bmp", c".bm"], [c"image/bmp", c"image/x-ms-bmp"],
+        c"Windows bitmap image.";
+    Ico => UDMF_META_ICO, Image, 12, [Image], [c".ico"], [c"image/ico", c"image/x-icon"],
+        c"Windows icon image.";
+    PhotoshopImage => UDMF_META_PHOTOSHOP_IMAGE, Image, 12, [Image], [c".psd"],
+        [c"image/x-photoshop", c"image/photoshop", c"image/psd", c"application/photoshop"],
+        c"Adobe Photoshop document.";
+    AiImage => UDMF_META_AI_IMAGE, Image, 12, [Image], [c".ai"], [], c"Adobe Illustrator document.";
+    WordDoc => UDMF_META_WORD_DOC, Document, 12, [CompositeObject], [c".doc"],
+        [c"application/msword"], c"Microsoft Word document.";
+    Excel => UDMF_META_EXCEL, Document, 12, [CompositeObject], [c".xls"],
+        [c"application/vnd.ms-excel"], c"Microsoft Excel spreadsheet.";
+    Ppt => UDMF_META_PPT, Document, 12, [CompositeObject], [c".ppt"],
+        [c"application/vnd.ms-powerpoint"], c"Microsoft PowerPoint presentation.";
+    Pdf => UDMF_META_PDF, Document, 12, [CompositeObject], [c".pdf"], [c"application/pdf"],
+        c"PDF data.";
+    Postscript => UDMF_META_POSTSCRIPT, Document, 12, [CompositeObject], [c".ps"],
+        [c"application/postscript"], c"PostScript document.";
+    EncapsulatedPostscript => UDMF_META_ENCAPSULATED_POSTSCRIPT, Document, 12, [Postscript],
+        [c".eps"], [], c"Encapsulated PostScript.";
+    Video => UDMF_META_VIDEO, Video, 12, [Media], [], [c"video/*"], c"Base type for video.";
+    Avi => UDMF_META_AVI, Video, 12, [Video], [c".avi", c".vfw"],
+        [c"video/avi", c"video/msvideo", c"video/x-msvideo"], c"AVI video.";
+    Mpeg => UDMF_META_MPEG, Video, 12, [Video], [c".mpg", c".mpeg", c".m75", c".m15", c".mpe"],
+        [c"video/mpg", c"video/mpeg", c"video/x-mpg", c"video/x-mpeg"], c"MPEG-1 or MPEG-2 video.";
+    Mpeg4 => UDMF_META_MPEG4, Video, 12, [Video], [c".mp4", c".mp4v", c".mpeg4"],
+        [c"video/mp4", c"video/mp4v"], c"MPEG-4 video.";
+    Video3gpp => UDMF_META_VIDEO_3GPP, Video, 12, [Video], [c".3gp", c".3gpp"], [c"video/3gpp"],
+        c"3GPP video.";
+    Video3gpp2 => UDMF_META_VIDEO_3GPP2, Video, 12, [Video], [c".3g2", c".3gp2", c".3gpp2"],
+        [c"video/3gpp2"], c"3GPP2 video.";
+    WindowsMediaWm => UDMF_META_WINDOWS_MEDIA_WM, Video, 12, [Video], [c".wm"], [c"video/x-ms-wm"],
+        c"Windows Media video.";
+    WindowsMediaWmv => UDMF_META_WINDOWS_MEDIA_WMV, Video, 12, [WindowsMediaWm], [c".wmv"],
+        [c"video/x-ms-wmv"], c"Windows Media WMV video.";
+    WindowsMediaWmp => UDMF_META_WINDOWS_MEDIA_WMP, Video, 12, [WindowsMediaWm], [c".wmp"],
+        [c"video/x-ms-wmp"], c"Windows Media WMP video.";
+    Audio => UDMF_META_AUDIO, Audio, 12, [Media], [], [c"audio/*"], c"Base type for audio.";
+    Aac => UDMF_META_AAC, Audio, 12, [Audio], [c".aac"], [c"audio/aac"], c"AAC audio.";
+    Aiff => UDMF_META_AIFF, Audio, 12, [Audio], [c".aiff"], [c"audio/aiff"], c"AIFF audio.";
+    Alac => UDMF_META_ALAC, Audio, 12, [Audio], [c".alac"], [c"audio/alac"], c"ALAC audio.";
+    Flac => UDMF_META_FLAC, Audio, 12, [Audio], [c".flac"], [c"audio/flac"], c"FLAC audio.";
+    Mp3 => UDMF_META_MP3, Audio, 12, [Audio], [c".mp3"], [c"audio/mp3"], c"MP3 audio.";
+    Ogg => UDMF_META_OGG, Audio, 12, [Audio], [c".ogg"], [c"audio/ogg"], c"OGG audio.";
+    Pcm => UDMF_META_PCM, Audio, 12, [Audio], [c".pcm"], [c"audio/pcm"], c"PCM audio.";
+    WindowsMediaWma => UDMF_META_WINDOWS_MEDIA_WMA, Audio, 12, [Audio], [c".wma"],
+        [c"audio/x-ms-wma"], c"Windows Media WMA audio.";
+    WaveformAudio => UDMF_META_WAVEFORM_AUDIO, Audio, 12, [Audio], [c".wav"],
+        [c"audio/wav", c"audio/wave", c"audio/x-wav"], c"Waveform audio.";
+    WindowsMediaWmx => UDMF_META_WINDOWS_MEDIA_WMX, Audio, 12, [Audio], [c".wmx"],
+        [c"video/x-ms-wmx"], c"Windows Media WMX playlist.";
+    WindowsMediaWvx => UDMF_META_WINDOWS_MEDIA_WVX, Audio, 12, [Audio], [c".wvx"],
+        [c"video/x-ms-wvx"], c"Windows Media WVX playlist.";
+    WindowsMediaWax => UDMF_META_WINDOWS_MEDIA_WAX, Audio, 12, [Audio], [c".wax"],
+        [c"audio/x-ms-wax"], c"Windows Media WAX playlist.";
+    GeneralFile => UDMF_META_GENERAL_FILE, General, 12, [Entity], [], [], c"Base type for file.";
+    Directory => UDMF_META_DIRECTORY, General, 12, [Entity], [], [], c"Base type for directory.";
+    Folder => UDMF_META_FOLDER, General, 12, [Directory], [], [], c"Base type for folder.";
+    Symlink => UDMF_META_SYMLINK, General, 12, [Entity], [], [], c"Base type for symbolic link.";
+    Archive => UDMF_META_ARCHIVE, Archive, 12, [Object], [], [], c"Base type for archive.";
+    Bz2Archive => UDMF_META_BZ2_ARCHIVE, Archive, 12, [Archive], [c".bz2", c".bzip2"],
+        [c"application/x-bzip2"], c"BZ2 archive.";
+    DiskImage => UDMF_META_DISK_IMAGE, Archive, 12, [Archive], [], [], c"Disk image archive.";
+    TarArchive => UDMF_META_TAR_ARCHIVE, Archive, 12, [Archive], [c".tar"],
+        [c"application/x-tar", c"application/tar"], c"Tar archive.";
+    ZipArchive => UDMF_META_ZIP_ARCHIVE, Archive, 12, [Archive], [c".zip"], [c"application/zip"],
+        c"Zip archive.";
+    JavaArchive => UDMF_META_JAVA_ARCHIVE, Archive, 12, [Archive, Executable], [c".jar"],
+        [c"application/java-archive"], c"Java archive.";
+    GnuTarArchive => UDMF_META_GNU_TAR_ARCHIVE, Archive, 12, [Archive], [c".gtar"],
+        [c"application/x-gtar"], c"GNU tar archive.";
+    GnuZipArchive => UDMF_META_GNU_ZIP_ARCHIVE, Archive, 12, [Archive], [c".gz", c".gzip"],
+        [c"application/x-gzip", c"application/gzip"], c"Gzip archive.";
+    GnuZipTarArchive => UDMF_META_GNU_ZIP_TAR_ARCHIVE, Archive, 12, [Archive], [c".tgz"],
+        [c"application/x-gtar"], c"Gzip tar archive.";
+    Calendar => UDMF_META_CALENDAR, General, 12, [Object], [], [c"text/calendar"],
+        c"Base type for scheduled events.";
+    Contact => UDMF_META_CONTACT, General, 12, [Object], [], [],
+        c"Base type for contact information.";
+    Database => UDMF_META_DATABASE, General, 12, [Object], [], [], c"Base type for databases.";
+    Message => UDMF_META_MESSAGE, General, 12, [Object], [], [], c"Base type for messages.";
+    Vcard => UDMF_META_VCARD, Text, 12, [Object], [c".vcf", c".vcard"],
+        [c"text/vcard", c"text/x-vcard"], c"Electronic business card.";
+    Navigation => UDMF_META_NAVIGATION, General, 12, [Object], [], [], c"Base type for navigation.";
+    Location => UDMF_META_LOCATION, General, 12, [Navigation], [], [], c"Navigation location.";
+    OpenHarmonyForm => UDMF_META_OPENHARMONY_FORM, OpenHarmony, 12, [Object], [], [],
+        c"OpenHarmony system defined form.";
+    OpenHarmonyAppItem => UDMF_META_OPENHARMONY_APP_ITEM, OpenHarmony, 12, [Object], [], [],
+        c"OpenHarmony system defined app item.";
+    OpenHarmonyPixelMap => UDMF_META_OPENHARMONY_PIXEL_MAP, OpenHarmony, 12, [Image], [], [],
+        c"OpenHarmony system defined pixel map.";
+    OpenHarmonyAtomicService => UDMF_META_OPENHARMONY_ATOMIC_SERVICE, OpenHarmony, 12, [Object], [],
+        [], c"OpenHarmony system defined atomic service.";
+    OpenHarmonyPackage => UDMF_META_OPENHARMONY_PACKAGE, OpenHarmony, 12, [Directory], [], [],
+        c"OpenHarmony system defined package.";
+    OpenHarmonyHap => UDMF_META_OPENHARMONY_HAP, OpenHarmony, 12, [OpenHarmonyPackage], [c".hap"],
+        [], c"OpenHarmony system defined ability package.";
+    Smil => UDMF_META_SMIL, Text, 12, [Xml], [c".smil"], [c"application/smil"],
+        c"Synchronized Multimedia Integration Language.";
+    Markdown => UDMF_META_MARKDOWN, Text, 12, [Text], [c".md", c".markdown", c".markdn", c".mdown"],
+        [c"text/markdown"], c"Markdown format.";
+    Fax => UDMF_META_FAX, Image, 12, [Image], [], [], c"Base type for fax images.";
+    JfxFax => UDMF_META_JFX_FAX, Image, 12, [Fax], [c".jfx"], [], c"J2 fax.";
+    EfxFax => UDMF_META_EFX_FAX, Image, 12, [Fax], [c".efx"], [c"image/efax"], c"eFax fax.";
+    XbitmapImage => UDMF_META_XBITMAP_IMAGE, Image, 12, [Image], [c".xbm"],
+        [c"image/x-xbitmap", c"image/x-xbm"], c"X bitmap image.";
+    TgaImage => UDMF_META_TGA_IMAGE, Image, 12, [Image], [c".tga"],
+        [c"image/targa", c"image/tga", c"application/tga"], c"TGA image.";
+    SgiImage => UDMF_META_SGI_IMAGE, Image, 12, [Image], [c".sgi"], [c"image/sgi"],
+        c"Silicon Graphics image.";
+    OpenexrImage => UDMF_META_OPENEXR_IMAGE, Image, 12, [Image], [c".exr"], [], c"OpenEXR image.";
+    FlashpixImage => UDMF_META_FLASHPIX_IMAGE, Image, 12, [Image], [c".fpx"],
+        [c"image/fpx", c"application/vnd.fpx"], c"FlashPix image.";
+    Realmedia => UDMF_META_REALMEDIA, Video, 12, [Video], [c".rm"],
+        [c"application/vnd.rn-realmedia"], c"RealMedia.";
+    AuAudio => UDMF_META_AU_AUDIO, Audio, 12, [Audio], [c".au", c".snd"],
+        [c"audio/basic", c"audio/au", c"audio/snd"], c"AU audio.";
+    AifcAudio => UDMF_META_AIFC_AUDIO, Audio, 12, [Audio], [c".aifc", c".aif"], [c"audio/x-aiff"],
+        c"AIFF-C audio.";
+    Sd2Audio => UDMF_META_SD2_AUDIO, Audio, 12, [Audio], [c".sd2"], [c"audio/x-sd2"],
+        c"Digidesign Sound Designer II audio.";
+    Realaudio => UDMF_META_REALAUDIO, Audio, 12, [Audio], [c".ram", c".ra"],
+        [c"audio/vnd.rn-realaudio", c"audio/x-pn-realaudio"], c"RealAudio.";
+    Openxml => UDMF_META_OPENXML, Document, 12, [Archive], [], [],
+        c"Base type for Office Open XML.";
+    WordprocessingmlDocument => UDMF_META_WORDPROCESSINGML_DOCUMENT, Document, 12,
+        [CompositeObject, Openxml], [c".docx"],
+        [c"application/vnd.openxmlformats-officedocument.wordprocessingml.document"],
+        c"Office Open XML document.";
+    SpreadsheetmlSheet => UDMF_META_SPREADSHEETML_SHEET, Document, 12, [CompositeObject, Openxml],
+        [c".xlsx"], [c"application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"],
+        c"Office Open XML workbook.";
+    PresentationmlPresentation => UDMF_META_PRESENTATIONML_PRESENTATION, Document, 12,
+        [CompositeObject, Openxml], [c".pptx"],
+        [c"application/vnd.openxmlformats-officedocument.presentationml.presentation"],
+        c"Office Open XML presentation.";
+    Opendocument => UDMF_META_OPENDOCUMENT, Document, 12, [Archive], [], [],
+        c"Base type for OpenDocument.";
+    OpendocumentText => UDMF_META_OPENDOCUMENT_TEXT, Document, 12, [CompositeObject, Opendocument],
+        [c".odt"], [c"application/vnd.oasis.opendocument.text"], c"OpenDocument text.";
+    OpendocumentSpreadsheet => UDMF_META_OPENDOCUMENT_SPREADSHEET, Document, 12,
+        [CompositeObject, Opendocument], [c".ods"],
+        [c"application/vnd.oasis.opendocument.spreadsheet"], c"OpenDocument spreadsheet.";
+    OpendocumentPresentation => UDMF_META_OPENDOCUMENT_PRESENTATION, Document, 12,
+        [CompositeObject, Opendocument], [c".odp"],
+        [c"application/vnd.oasis.opendocument.presentation"], c"OpenDocument presentation.";
+    OpendocumentGraphics => UDMF_META_OPENDOCUMENT_GRAPHICS, Document, 12,
+        [CompositeObject, Opendocument], [c".odg"],
+        [c"application/vnd.oasis.opendocument.graphics"], c"OpenDocument graphics.";
+    OpendocumentFormula => UDMF_META_OPENDOCUMENT_FORMULA, Document, 12, [Opendocument], [c".odf"],
+        [c"application/vnd.oasis.opendocument.formula"], c"OpenDocument formula.";
+    StuffitArchive => UDMF_META_STUFFIT_ARCHIVE, Archive, 12, [Archive], [c".sit", c".sitx"],
+        [c"application/x-stuffit", c"application/x-sit", c"application/stuffit"],
+        c"StuffIt archive.";
+    Vcs => UDMF_META_VCS, Text, 12, [Calendar], [c".vcs"], [c"text/calendar"], c"vCalendar event.";
+    Ics => UDMF_META_ICS, Text, 12, [Calendar], [c".ics"], [c"text/calendar"], c"iCalendar event.";
+    Executable => UDMF_META_EXECUTABLE, General, 12, [Object], [], [],
+        c"Base type for executables.";
+    PortableExecutable => UDMF_META_PORTABLE_EXECUTABLE, General, 12, [Executable],
+        [c".exe", c".dll"], [c"application/vnd.microsoft.portable-executable"],
+        c"Microsoft Windows application.";
+    SunJavaClass => UDMF_META_SUN_JAVA_CLASS, General, 12, [Executable], [c".class"], [],
+        c"Java class file.";
+    Font => UDMF_META_FONT, Font, 12, [Object], [], [c"font/*"], c"Base type for fonts.";
+    TruetypeFont => UDMF_META_TRUETYPE_FONT, Font, 12, [Font], [c".ttf"], [c"font/ttf"],
+        c"TrueType font.";
+    TruetypeCollectionFont => UDMF_META_TRUETYPE_COLLECTION_FONT, Font, 12, [Font], [c".ttc"],
+        [c"font/collection"], c"TrueType collection font.";
+    OpentypeFont => UDMF_META_OPENTYPE_FONT, Font, 12, [Font], [c".otf"], [c"font/otf"],
+        c"OpenType font.";
+    PostscriptFont => UDMF_META_POSTSCRIPT_FONT, Font, 12, [Font], [], [], c"PostScript font.";
+    PostscriptPfbFont => UDMF_META_POSTSCRIPT_PFB_FONT, Font, 12, [PostscriptFont], [c".pfb"],
+        [c"application/x-font"], c"PostScript Font Binary font.";
+    PostscriptPfaFont => UDMF_META_POSTSCRIPT_PFA_FONT, Font, 12, [PostscriptFont], [c".pfa"],
+        [c"application/x-font"], c"PostScript Font ASCII font.";
+    OpenHarmonyHdoc => UDMF_META_OPENHARMONY_HDOC, OpenHarmony, 12, [CompositeObject], [c".hdoc"],
+        [], c"OpenHarmony system defined note document.";
+    OpenHarmonyHinote => UDMF_META_OPENHARMONY_HINOTE, OpenHarmony, 12, [CompositeObject],
+        [c".hinote"], [], c"OpenHarmony system defined note.";
+    OpenHarmonyStyledString => UDMF_META_OPENHARMONY_STYLED_STRING, OpenHarmony, 12,
+        [CompositeObject], [], [], c"OpenHarmony system defined styled string.";
+    OpenHarmonyWant => UDMF_META_OPENHARMONY_WANT, OpenHarmony, 12, [Object], [], [],
+        c"OpenHarmony system defined Want.";
+    GeneralFileUri => UDMF_META_GENERAL_FILE_URI, General, 13, [Text], [], [], c"File URI.";
+    GeneralContentForm => UDMF_METE_GENERAL_CONTENT_FORM, General, 14, [Object], [], [],
+        c"Content form.";
+}
+
+impl UniformDataType {
+    /// Iterate over all known variants, i.e. all except [`Other`](Self::Other).
+    pub fn all() -> impl Iterator<Item = &'static UniformDataType> {
+        KNOWN_TYPES.iter().map(|metadata| &metadata.type_id)
+    }
+
+    /// Get the static metadata of a known variant, or `None` for [`Other`](Self::Other).
+    pub fn metadata(&self) -> Option<&'static TypeMetadata> {
+        table_index(self).map(|index| &KNOWN_TYPES[index])
+    }
+
+    /// Get the category of a known variant, or `None` for [`Other`](Self::Other).
+    pub fn category(&self) -> Option<TypeCategory> {
+        self.metadata().map(|metadata| metadata.category)
+    }
+
+    /// Get the direct parents of a known type in the standard UTD hierarchy.
+    ///
+    /// Returns an empty slice for [`Other`](Self::Other).
+    pub fn belonging_to_types(&self) -> &'static [UniformDataType] {
+        self.metadata()
+            .map_or(&[], |metadata| metadata.belonging_to_types)
+    }
+
+    /// Check whether this type is `other` or one of its descendants in the standard UTD
+    /// hierarchy, without calling into the platform.
+    ///
+    /// Custom types only belong to themselves; use
+    /// [`TypeDescriptor::belongs_to`](crate::TypeDescriptor::belongs_to) for those.
+    pub fn belongs_to(&self, other: &UniformDataType) -> bool {
+        let mut visited = HashSet::new();
+        let mut queue = VecDeque::from([self]);
+        while let Some(type_id) = queue.pop_front() {
+            if type_id == other {
+                return true;
+            }
+            if visited.insert(type_id) {
+                queue.extend(type_id.belonging_to_types());
+            }
+        }
+        false
+    }
+
+    /// Get the MIME types of a known type. Empty for [`Other`](Self::Other).
+    pub fn mime_types(&self) -> impl Iterator<Item = &'static str> {
+        self.metadata()
+            .map_or(&[][..], |metadata| metadata.mime_types)
+            .iter()
+            .map(|mime_type| ascii_str(mime_type))
+    }
+
+    /// Get the filename extensions of a known type, including the leading dot.
+    ///
+    /// Empty for [`Other`](Self::Other).
+    pub fn filename_extensions(&self) -> impl Iterator<Item = &'static str> {
+        self.metadata()
+            .map_or(&[][..], |metadata| metadata.filename_extensions)
+            .iter()
+            .map(|extension| ascii_str(extension))
+    }
+}
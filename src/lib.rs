@@ -1,11 +1,31 @@
+pub mod entry;
 pub mod error;
+#[cfg(feature = "api-15")]
+pub mod get_data;
+pub mod known_types;
+#[cfg(feature = "mock")]
+mod mock;
+pub mod pixel_map;
+pub mod properties;
+mod provider;
+pub mod snapshot;
+pub mod store;
+pub mod type_cache;
 pub mod types;
 pub mod uds;
 pub mod unified_data;
 pub mod utd;
 
+pub use entry::Entry;
 pub use error::{Result, UdmfError};
+pub use known_types::{TypeCategory, TypeMetadata};
+pub use pixel_map::{ImageInfo, NativePixelMap, PixelFormat};
+pub use properties::{ShareOption, UnifiedDataProperties};
+pub use store::{DataKey, Intention};
+pub use type_cache::TypeCache;
 pub use types::UniformDataType;
-pub use uds::{AppItem, ArrayBuffer, ContentForm, FileUri, Html, Hyperlink, PixelMap, PlainText};
-pub use unified_data::{UnifiedData, UnifiedRecord};
-pub use utd::TypeDescriptor;
+pub use uds::{
+    AppItem, ArrayBuffer, ContentForm, FileUri, Html, Hyperlink, PixelMap, PlainText, UdsObject,
+};
+pub use unified_data::{RecordRef, UnifiedData, UnifiedRecord};
+pub use utd::{Ancestors, TypeDescriptor, TypeInfo};
@@ -0,0 +1,801 @@
+//! Mock unified data, records, record providers and properties.
+
+use super::uds::{ARRAY_BUFFER_DATA, Kind, Uds, uds};
+use super::{E_INVALID_PARAM, E_OK, ERR, StringList, c_str, handle, write_out};
+use ohos_sys_opaque_types::*;
+use std::collections::HashMap;
+use std::ffi::{CStr, CString, c_char, c_int, c_uchar, c_uint, c_void};
+use std::ptr;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{SystemTime, UNIX_EPOCH};
+use udmf_sys::data_management_framework::*;
+
+/// Lock `mutex`, ignoring poisoning. The mock state stays consistent across panics.
+pub(super) fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[derive(Clone)]
+enum Value {
+    Uds(Uds),
+    Bytes(Vec<u8>),
+}
+
+/// The callback and context registered with `OH_UdmfRecordProvider_SetData`.
+struct Provider {
+    context: *mut c_void,
+    callback: OH_UdmfRecordProvider_GetData,
+    finalize: UdmfData_Finalize,
+}
+
+impl Drop for Provider {
+    fn drop(&mut self) {
+        if let Some(finalize) = self.finalize {
+            // SAFETY: the finalizer was registered together with the context.
+            unsafe { finalize(self.context) };
+        }
+    }
+}
+
+// SAFETY: the context is only passed to the callbacks it was registered with, which the
+// provider API requires to be callable from any thread.
+unsafe impl Send for Provider {}
+// SAFETY: see above.
+unsafe impl Sync for Provider {}
+
+/// The handle behind `OH_UdmfRecordProvider`. It is empty until the data is set.
+#[derive(Default)]
+struct ProviderHandle(Option<Provider>);
+
+/// The entries of a record, shared between all handles to the record.
+#[derive(Default)]
+pub(super) struct RecordState {
+    entries: Vec<(CString, Value)>,
+    providers: Vec<(CString, Arc<Provider>)>,
+}
+
+pub(super) type SharedRecord = Arc<Mutex<RecordState>>;
+
+impl RecordState {
+    fn add(&mut self, type_id: &CStr, value: Value) {
+        match self
+            .entries
+            .iter_mut()
+            .find(|(t, _)| t.as_c_str() == type_id)
+        {
+            Some((_, existing)) => *existing = value,
+            None => self.entries.push((type_id.to_owned(), value)),
+        }
+    }
+
+    /// The types of all entries, including the ones a provider has not produced yet.
+    fn types(&self) -> Vec<CString> {
+        let mut types: Vec<CString> = self.entries.iter().map(|(t, _)| t.clone()).collect();
+        for (type_id, _) in &self.providers {
+            if !types.contains(type_id) {
+                types.push(type_id.clone());
+            }
+        }
+        types
+    }
+}
+
+/// Get the value stored under `type_id`, asking a provider for it if necessary.
+fn get_value(record: &SharedRecord, type_id: &CStr) -> Option<Value> {
+    let provider = {
+        let state = lock(record);
+        if let Some((_, value)) = state.entries.iter().find(|(t, _)| t.as_c_str() == type_id) {
+            return Some(value.clone());
+        }
+        let (_, provider) = state
+            .providers
+            .iter()
+            .find(|(t, _)| t.as_c_str() == type_id)?;
+        Arc::clone(provider)
+    };
+    // The provider is called without holding the lock, so that it may read the record itself.
+    let callback = provider.callback?;
+    // SAFETY: the callback was registered together with the context.
+    let ptr = unsafe { callback(provider.context, type_id.as_ptr()) };
+    if ptr.is_null() {
+        return None;
+    }
+    // SAFETY: providers return a UDS object created by the mock and pass its ownership.
+    let uds = *unsafe { Box::from_raw(ptr.cast::<Uds>()) };
+    let value = if uds.kind == Kind::ArrayBuffer {
+        Value::Bytes(uds.bytes(ARRAY_BUFFER_DATA).to_vec())
+    } else {
+        Value::Uds(uds)
+    };
+    lock(record).add(type_id, value.clone());
+    Some(value)
+}
+
+/// Copy all entries of `record` into a new record. Lazy entries are produced first.
+pub(super) fn snapshot(record: &SharedRecord) -> SharedRecord {
+    let types = lock(record).types();
+    let mut copy = RecordState::default();
+    for type_id in types {
+        if let Some(value) = get_value(record, &type_id) {
+            copy.add(&type_id, value);
+        }
+    }
+    Arc::new(Mutex::new(copy))
+}
+
+/// The handle behind `OH_UdmfRecord`.
+struct Record {
+    state: SharedRecord,
+    types: StringList,
+    general_entry: Vec<u8>,
+}
+
+impl Record {
+    fn new(state: SharedRecord) -> Self {
+        Self {
+            state,
+            types: StringList::default(),
+            general_entry: Vec::new(),
+        }
+    }
+}
+
+#[derive(Clone)]
+enum Extra {
+    Int(c_int),
+    String(CString),
+}
+
+#[derive(Clone)]
+pub(super) struct Properties {
+    tag: CString,
+    timestamp: i64,
+    share_option: Udmf_ShareOption,
+    extras: HashMap<CString, Extra>,
+}
+
+pub(super) type SharedProperties = Arc<Mutex<Properties>>;
+
+impl Default for Properties {
+    fn default() -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_millis() as i64);
+        Self {
+            tag: CString::default(),
+            timestamp,
+            share_option: Udmf_ShareOption::CROSS_APP,
+            extras: HashMap::new(),
+        }
+    }
+}
+
+/// The handle behind `OH_UdmfData`.
+pub(super) struct Data {
+    records: Vec<SharedRecord>,
+    /// Borrowed record handles handed out to callers, destroyed together with the data.
+    handles: Vec<*mut OH_UdmfRecord>,
+    types: StringList,
+    pub(super) properties: SharedProperties,
+}
+
+impl Data {
+    pub(super) fn new(records: Vec<SharedRecord>, properties: SharedProperties) -> Self {
+        let mut data = Self {
+            records: Vec::new(),
+            handles: Vec::new(),
+            types: StringList::default(),
+            properties,
+        };
+        data.set_records(records);
+        data
+    }
+
+    pub(super) fn records(&self) -> &[SharedRecord] {
+        &self.records
+    }
+
+    pub(super) fn set_records(&mut self, records: Vec<SharedRecord>) {
+        self.destroy_handles();
+        for record in records {
+            self.push_record(record);
+        }
+    }
+
+    fn push_record(&mut self, record: SharedRecord) {
+        let handle = Box::into_raw(Box::new(Record::new(Arc::clone(&record))));
+        self.handles.push(handle.cast());
+        self.records.push(record);
+    }
+
+    fn destroy_handles(&mut self) {
+        for handle in self.handles.drain(..) {
+            // SAFETY: the handles were created from a `Box<Record>` by `push_record`.
+            drop(unsafe { Box::from_raw(handle.cast::<Record>()) });
+        }
+        self.records.clear();
+    }
+
+    fn types(&self) -> Vec<CString> {
+        let mut types = Vec::new();
+        for record in &self.records {
+            for type_id in lock(record).types() {
+                if !types.contains(&type_id) {
+                    types.push(type_id);
+                }
+            }
+        }
+        types
+    }
+}
+
+impl Drop for Data {
+    fn drop(&mut self) {
+        self.destroy_handles();
+    }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_UdmfData_Create() -> *mut OH_UdmfData {
+    let data = Data::new(Vec::new(), SharedProperties::default());
+    Box::into_raw(Box::new(data)).cast()
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_UdmfData_Destroy(this: *mut OH_UdmfData) {
+    if !this.is_null() {
+        // SAFETY: the handle was created from a `Box<Data>` and is destroyed only once.
+        drop(unsafe { Box::from_raw(this.cast::<Data>()) });
+    }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_UdmfData_AddRecord(this: *mut OH_UdmfData, record: *mut OH_UdmfRecord) -> c_int {
+    // SAFETY: the caller passes handles created by the mock, or nulls.
+    match unsafe { (handle::<Data, _>(this), handle::<Record, _>(record)) } {
+        (Some(data), Some(record)) => {
+            data.push_record(Arc::clone(&record.state));
+            E_OK
+        }
+        _ => E_INVALID_PARAM,
+    }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_UdmfData_HasType(this: *mut OH_UdmfData, type_id: *const c_char) -> bool {
+    // SAFETY: the caller passes a handle created by the mock and a C string, or nulls.
+    match unsafe { (handle::<Data, _>(this), c_str(type_id)) } {
+        (Some(data), Some(type_id)) => data.types().iter().any(|t| t.as_c_str() == type_id),
+        _ => false,
+    }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_UdmfData_GetTypes(this: *mut OH_UdmfData, count: *mut c_uint) -> *mut *mut c_char {
+    // SAFETY: the caller passes a handle created by the mock, or null.
+    let Some(data) = (unsafe { handle::<Data, _>(this) }) else {
+        return ptr::null_mut();
+    };
+    let types = data.types();
+    // SAFETY: count is a valid out parameter or null.
+    unsafe { data.types.replace(types, count) }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_UdmfData_GetRecords(
+    this: *mut OH_UdmfData,
+    count: *mut c_uint,
+) -> *mut *mut OH_UdmfRecord {
+    // SAFETY: the caller passes a handle created by the mock, or null.
+    let Some(data) = (unsafe { handle::<Data, _>(this) }) else {
+        return ptr::null_mut();
+    };
+    // SAFETY: count is a valid out parameter or null.
+    unsafe { write_out(count, data.handles.len() as c_uint) };
+    if data.handles.is_empty() {
+        ptr::null_mut()
+    } else {
+        data.handles.as_mut_ptr()
+    }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_UdmfData_GetRecordCount(this: *mut OH_UdmfData) -> c_int {
+    // SAFETY: the caller passes a handle created by the mock, or null.
+    unsafe { handle::<Data, _>(this) }.map_or(0, |data| data.records.len() as c_int)
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_UdmfData_GetRecord(this: *mut OH_UdmfData, index: c_uint) -> *mut OH_UdmfRecord {
+    // SAFETY: the caller passes a handle created by the mock, or null.
+    unsafe { handle::<Data, _>(this) }
+        .and_then(|data| data.handles.get(index as usize).copied())
+        .unwrap_or(ptr::null_mut())
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_UdmfData_IsLocal(this: *mut OH_UdmfData) -> bool {
+    // SAFETY: the caller passes a handle created by the mock, or null.
+    unsafe { handle::<Data, _>(this) }.is_some()
+}
+
+/// Copy the first entry of `kind` found in the records of `data` into `out`.
+///
+/// # Safety
+///
+/// `data` and `out` must be null or handles created by the mock.
+unsafe fn get_primary<T>(data: *mut OH_UdmfData, out: *mut T, kind: Kind) -> c_int {
+    // SAFETY: guaranteed by the caller.
+    let (Some(data), Some(out)) = (unsafe { handle::<Data, _>(data) }, unsafe {
+        uds(out, kind)
+    }) else {
+        return E_INVALID_PARAM;
+    };
+    for record in &data.records {
+        if let Some(Value::Uds(value)) = get_value(record, kind.type_id()) {
+            *out = value;
+            return E_OK;
+        }
+    }
+    ERR
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_UdmfData_GetPrimaryPlainText(
+    data: *mut OH_UdmfData,
+    plain_text: *mut OH_UdsPlainText,
+) -> c_int {
+    // SAFETY: the caller passes handles created by the mock, or nulls.
+    unsafe { get_primary(data, plain_text, Kind::PlainText) }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_UdmfData_GetPrimaryHtml(data: *mut OH_UdmfData, html: *mut OH_UdsHtml) -> c_int {
+    // SAFETY: the caller passes handles created by the mock, or nulls.
+    unsafe { get_primary(data, html, Kind::Html) }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_UdmfRecord_Create() -> *mut OH_UdmfRecord {
+    Box::into_raw(Box::new(Record::new(SharedRecord::default()))).cast()
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_UdmfRecord_Destroy(this: *mut OH_UdmfRecord) {
+    if !this.is_null() {
+        // SAFETY: the handle was created from a `Box<Record>` and is destroyed only once.
+        drop(unsafe { Box::from_raw(this.cast::<Record>()) });
+    }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_UdmfRecord_GetTypes(
+    this: *mut OH_UdmfRecord,
+    count: *mut c_uint,
+) -> *mut *mut c_char {
+    // SAFETY: the caller passes a handle created by the mock, or null.
+    let Some(record) = (unsafe { handle::<Record, _>(this) }) else {
+        return ptr::null_mut();
+    };
+    let types = lock(&record.state).types();
+    // SAFETY: count is a valid out parameter or null.
+    unsafe { record.types.replace(types, count) }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_UdmfRecord_AddGeneralEntry(
+    this: *mut OH_UdmfRecord,
+    type_id: *const c_char,
+    entry: *mut c_uchar,
+    count: c_uint,
+) -> c_int {
+    // SAFETY: the caller passes a handle created by the mock and a C string, or nulls.
+    let (Some(record), Some(type_id)) = (unsafe { handle::<Record, _>(this) }, unsafe {
+        c_str(type_id)
+    }) else {
+        return E_INVALID_PARAM;
+    };
+    let bytes = match (entry.is_null(), count) {
+        (_, 0) => Vec::new(),
+        (true, _) => return E_INVALID_PARAM,
+        // SAFETY: the caller passes `count` readable bytes.
+        (false, count) => unsafe { std::slice::from_raw_parts(entry, count as usize) }.to_vec(),
+    };
+    lock(&record.state).add(type_id, Value::Bytes(bytes));
+    E_OK
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_UdmfRecord_GetGeneralEntry(
+    this: *mut OH_UdmfRecord,
+    type_id: *const c_char,
+    entry: *mut *mut c_uchar,
+    count: *mut c_uint,
+) -> c_int {
+    // SAFETY: the caller passes a handle created by the mock and a C string, or nulls.
+    let (Some(record), Some(type_id)) = (unsafe { handle::<Record, _>(this) }, unsafe {
+        c_str(type_id)
+    }) else {
+        return E_INVALID_PARAM;
+    };
+    let Some(Value::Bytes(bytes)) = get_value(&record.state, type_id) else {
+        return ERR;
+    };
+    // The bytes stay valid until the next call on this record handle.
+    record.general_entry = bytes;
+    // SAFETY: entry and count are valid out parameters or null.
+    unsafe {
+        write_out(entry, record.general_entry.as_mut_ptr());
+        write_out(count, record.general_entry.len() as c_uint);
+    }
+    E_OK
+}
+
+/// Add a copy of the UDS object `value` of `kind` to `record`.
+///
+/// # Safety
+///
+/// `record` and `value` must be null or handles created by the mock.
+unsafe fn add_uds<T>(record: *mut OH_UdmfRecord, value: *mut T, kind: Kind) -> c_int {
+    // SAFETY: guaranteed by the caller.
+    match unsafe { (handle::<Record, _>(record), uds(value, kind)) } {
+        (Some(record), Some(value)) => {
+            lock(&record.state).add(kind.type_id(), Value::Uds(value.clone()));
+            E_OK
+        }
+        _ => E_INVALID_PARAM,
+    }
+}
+
+/// Copy the entry of `kind` of `record` into the UDS object `out`.
+///
+/// # Safety
+///
+/// `record` and `out` must be null or handles created by the mock.
+unsafe fn get_uds<T>(record: *mut OH_UdmfRecord, out: *mut T, kind: Kind) -> c_int {
+    // SAFETY: guaranteed by the caller.
+    let (Some(record), Some(out)) = (unsafe { handle::<Record, _>(record) }, unsafe {
+        uds(out, kind)
+    }) else {
+        return E_INVALID_PARAM;
+    };
+    match get_value(&record.state, kind.type_id()) {
+        Some(Value::Uds(value)) if value.kind == kind => {
+            *out = value;
+            E_OK
+        }
+        _ => ERR,
+    }
+}
+
+macro_rules! record_uds {
+    ($raw:ty, $kind:ident, $add:ident, $get:ident) => {
+        #[unsafe(no_mangle)]
+        extern "C" fn $add(this: *mut OH_UdmfRecord, value: *mut $raw) -> c_int {
+            // SAFETY: the caller passes handles created by the mock, or nulls.
+            unsafe { add_uds(this, value, Kind::$kind) }
+        }
+
+        #[unsafe(no_mangle)]
+        extern "C" fn $get(this: *mut OH_UdmfRecord, value: *mut $raw) -> c_int {
+            // SAFETY: the caller passes handles created by the mock, or nulls.
+            unsafe { get_uds(this, value, Kind::$kind) }
+        }
+    };
+}
+
+record_uds!(
+    OH_UdsPlainText,
+    PlainText,
+    OH_UdmfRecord_AddPlainText,
+    OH_UdmfRecord_GetPlainText
+);
+record_uds!(
+    OH_UdsHyperlink,
+    Hyperlink,
+    OH_UdmfRecord_AddHyperlink,
+    OH_UdmfRecord_GetHyperlink
+);
+record_uds!(
+    OH_UdsHtml,
+    Html,
+    OH_UdmfRecord_AddHtml,
+    OH_UdmfRecord_GetHtml
+);
+record_uds!(
+    OH_UdsAppItem,
+    AppItem,
+    OH_UdmfRecord_AddAppItem,
+    OH_UdmfRecord_GetAppItem
+);
+record_uds!(
+    OH_UdsFileUri,
+    FileUri,
+    OH_UdmfRecord_AddFileUri,
+    OH_UdmfRecord_GetFileUri
+);
+record_uds!(
+    OH_UdsPixelMap,
+    PixelMap,
+    OH_UdmfRecord_AddPixelMap,
+    OH_UdmfRecord_GetPixelMap
+);
+record_uds!(
+    OH_UdsContentForm,
+    ContentForm,
+    OH_UdmfRecord_AddContentForm,
+    OH_UdmfRecord_GetContentForm
+);
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_UdmfRecord_AddArrayBuffer(
+    record: *mut OH_UdmfRecord,
+    type_id: *const c_char,
+    buffer: *mut OH_UdsArrayBuffer,
+) -> c_int {
+    // SAFETY: the caller passes handles created by the mock and a C string, or nulls.
+    match unsafe {
+        (
+            handle::<Record, _>(record),
+            c_str(type_id),
+            uds(buffer, Kind::ArrayBuffer),
+        )
+    } {
+        (Some(record), Some(type_id), Some(buffer)) => {
+            let bytes = buffer.bytes(ARRAY_BUFFER_DATA).to_vec();
+            lock(&record.state).add(type_id, Value::Bytes(bytes));
+            E_OK
+        }
+        _ => E_INVALID_PARAM,
+    }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_UdmfRecord_GetArrayBuffer(
+    record: *mut OH_UdmfRecord,
+    type_id: *const c_char,
+    buffer: *mut OH_UdsArrayBuffer,
+) -> c_int {
+    // SAFETY: the caller passes handles created by the mock and a C string, or nulls.
+    let (Some(record), Some(type_id), Some(buffer)) = (unsafe {
+        (
+            handle::<Record, _>(record),
+            c_str(type_id),
+            uds(buffer, Kind::ArrayBuffer),
+        )
+    }) else {
+        return E_INVALID_PARAM;
+    };
+    match get_value(&record.state, type_id) {
+        Some(Value::Bytes(bytes)) => {
+            *buffer = Uds::array_buffer(bytes);
+            E_OK
+        }
+        _ => ERR,
+    }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_UdmfRecordProvider_Create() -> *mut OH_UdmfRecordProvider {
+    Box::into_raw(Box::new(ProviderHandle::default())).cast()
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_UdmfRecordProvider_Destroy(provider: *mut OH_UdmfRecordProvider) -> c_int {
+    if provider.is_null() {
+        return E_INVALID_PARAM;
+    }
+    // SAFETY: the handle was created from a `Box<ProviderHandle>` and is destroyed only once.
+    drop(unsafe { Box::from_raw(provider.cast::<ProviderHandle>()) });
+    E_OK
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_UdmfRecordProvider_SetData(
+    provider: *mut OH_UdmfRecordProvider,
+    context: *mut c_void,
+    callback: OH_UdmfRecordProvider_GetData,
+    finalize: UdmfData_Finalize,
+) -> c_int {
+    // SAFETY: the caller passes a handle created by the mock, or null.
+    let Some(provider) = (unsafe { handle::<ProviderHandle, _>(provider) }) else {
+        return E_INVALID_PARAM;
+    };
+    if callback.is_none() {
+        return E_INVALID_PARAM;
+    }
+    provider.0 = Some(Provider {
+        context,
+        callback,
+        finalize,
+    });
+    E_OK
+}
+
+/// The record takes ownership of `provider` and releases it once the record is destroyed.
+#[unsafe(no_mangle)]
+extern "C" fn OH_UdmfRecord_SetProvider(
+    this: *mut OH_UdmfRecord,
+    types: *const *const c_char,
+    count: c_uint,
+    provider: *mut OH_UdmfRecordProvider,
+) -> c_int {
+    // SAFETY: the caller passes handles created by the mock, or nulls.
+    let (Some(record), Some(handle)) = (unsafe { handle::<Record, _>(this) }, unsafe {
+        handle::<ProviderHandle, _>(provider)
+    }) else {
+        return E_INVALID_PARAM;
+    };
+    if types.is_null() || count == 0 || handle.0.is_none() {
+        return E_INVALID_PARAM;
+    }
+    // SAFETY: the caller passes `count` C strings.
+    let types = unsafe { std::slice::from_raw_parts(types, count as usize) };
+    // SAFETY: the type ids are valid C strings or null.
+    let Some(types) = types
+        .iter()
+        .map(|t| unsafe { c_str(*t) })
+        .collect::<Option<Vec<_>>>()
+    else {
+        return E_INVALID_PARAM;
+    };
+    // SAFETY: the handle was created from a `Box<ProviderHandle>` and is consumed here.
+    let owned = unsafe { Box::from_raw(provider.cast::<ProviderHandle>()) };
+    let shared = Arc::new(owned.0.expect("provider data is set"));
+    let mut state = lock(&record.state);
+    for type_id in types {
+        state
+            .providers
+            .push((type_id.to_owned(), Arc::clone(&shared)));
+    }
+    E_OK
+}
+
+/// The handle behind `OH_UdmfProperty`.
+struct Property {
+    properties: SharedProperties,
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_UdmfProperty_Create(data: *mut OH_UdmfData) -> *mut OH_UdmfProperty {
+    // SAFETY: the caller passes a handle created by the mock, or null.
+    let Some(data) = (unsafe { handle::<Data, _>(data) }) else {
+        return ptr::null_mut();
+    };
+    let property = Property {
+        properties: Arc::clone(&data.properties),
+    };
+    Box::into_raw(Box::new(property)).cast()
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_UdmfProperty_Destroy(this: *mut OH_UdmfProperty) {
+    if !this.is_null() {
+        // SAFETY: the handle was created from a `Box<Property>` and is destroyed only once.
+        drop(unsafe { Box::from_raw(this.cast::<Property>()) });
+    }
+}
+
+/// Lock the properties behind `this`.
+///
+/// # Safety
+///
+/// `this` must be null or a handle created by the mock.
+unsafe fn properties<'a>(this: *mut OH_UdmfProperty) -> Option<MutexGuard<'a, Properties>> {
+    // SAFETY: guaranteed by the caller.
+    unsafe { handle::<Property, _>(this) }.map(|property| lock(&property.properties))
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_UdmfProperty_GetTag(this: *mut OH_UdmfProperty) -> *const c_char {
+    // The tag stays valid until it is replaced.
+    // SAFETY: the caller passes a handle created by the mock, or null.
+    unsafe { properties(this) }.map_or(ptr::null(), |p| p.tag.as_ptr())
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_UdmfProperty_SetTag(this: *mut OH_UdmfProperty, tag: *const c_char) -> c_int {
+    // SAFETY: the caller passes a handle created by the mock and a C string, or nulls.
+    match unsafe { (properties(this), c_str(tag)) } {
+        (Some(mut properties), Some(tag)) => {
+            properties.tag = tag.to_owned();
+            E_OK
+        }
+        _ => E_INVALID_PARAM,
+    }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_UdmfProperty_GetTimestamp(this: *mut OH_UdmfProperty) -> i64 {
+    // SAFETY: the caller passes a handle created by the mock, or null.
+    unsafe { properties(this) }.map_or(0, |p| p.timestamp)
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_UdmfProperty_GetShareOption(this: *mut OH_UdmfProperty) -> Udmf_ShareOption {
+    // SAFETY: the caller passes a handle created by the mock, or null.
+    unsafe { properties(this) }.map_or(Udmf_ShareOption::INVALID, |p| p.share_option)
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_UdmfProperty_SetShareOption(
+    this: *mut OH_UdmfProperty,
+    option: Udmf_ShareOption,
+) -> c_int {
+    // SAFETY: the caller passes a handle created by the mock, or null.
+    match unsafe { properties(this) } {
+        Some(mut properties)
+            if option == Udmf_ShareOption::IN_APP || option == Udmf_ShareOption::CROSS_APP =>
+        {
+            properties.share_option = option;
+            E_OK
+        }
+        _ => E_INVALID_PARAM,
+    }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_UdmfProperty_GetExtrasIntParam(
+    this: *mut OH_UdmfProperty,
+    key: *const c_char,
+    default_value: c_int,
+) -> c_int {
+    // SAFETY: the caller passes a handle created by the mock and a C string, or nulls.
+    match unsafe { (properties(this), c_str(key)) } {
+        (Some(properties), Some(key)) => match properties.extras.get(key) {
+            Some(Extra::Int(value)) => *value,
+            _ => default_value,
+        },
+        _ => default_value,
+    }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_UdmfProperty_SetExtrasIntParam(
+    this: *mut OH_UdmfProperty,
+    key: *const c_char,
+    param: c_int,
+) -> c_int {
+    // SAFETY: the caller passes a handle created by the mock and a C string, or nulls.
+    match unsafe { (properties(this), c_str(key)) } {
+        (Some(mut properties), Some(key)) => {
+            properties.extras.insert(key.to_owned(), Extra::Int(param));
+            E_OK
+        }
+        _ => E_INVALID_PARAM,
+    }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_UdmfProperty_GetExtrasStringParam(
+    this: *mut OH_UdmfProperty,
+    key: *const c_char,
+) -> *const c_char {
+    // The value stays valid until it is replaced.
+    // SAFETY: the caller passes a handle created by the mock and a C string, or nulls.
+    match unsafe { (properties(this), c_str(key)) } {
+        (Some(properties), Some(key)) => match properties.extras.get(key) {
+            Some(Extra::String(value)) => value.as_ptr(),
+            _ => ptr::null(),
+        },
+        _ => ptr::null(),
+    }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_UdmfProperty_SetExtrasStringParam(
+    this: *mut OH_UdmfProperty,
+    key: *const c_char,
+    param: *const c_char,
+) -> c_int {
+    // SAFETY: the caller passes a handle created by the mock and C strings, or nulls.
+    match unsafe { (properties(this), c_str(key), c_str(param)) } {
+        (Some(mut properties), Some(key), Some(param)) => {
+            properties
+                .extras
+                .insert(key.to_owned(), Extra::String(param.to_owned()));
+            E_OK
+        }
+        _ => E_INVALID_PARAM,
+    }
+}
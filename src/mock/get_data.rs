@@ -0,0 +1,116 @@
+//! Mock parameters of asynchronous data retrieval.
+//!
+//! The retrieval itself is started by ArkUI, which the mock does not provide. The parameters
+//! only keep their progress listener, which tests drive with [`report_progress`].
+
+use super::handle;
+use ohos_sys_opaque_types::{OH_UdmfData, OH_UdmfGetDataParams};
+use std::ffi::{c_char, c_int};
+use udmf_sys::Udmf_ListenerStatus;
+use udmf_sys::data_management_framework::*;
+
+/// The handle behind `OH_UdmfGetDataParams`.
+#[derive(Default)]
+struct GetDataParams {
+    listener: OH_Udmf_DataProgressListener,
+}
+
+/// The handle behind `OH_Udmf_ProgressInfo`.
+struct ProgressInfo {
+    progress: c_int,
+    status: Udmf_ListenerStatus,
+}
+
+/// Check that `ptr` is a parameters handle.
+///
+/// # Safety
+///
+/// `ptr` must be null or a handle created by the mock.
+unsafe fn is_params(ptr: *mut OH_UdmfGetDataParams) -> bool {
+    // SAFETY: guaranteed by the caller.
+    unsafe { handle::<GetDataParams, _>(ptr) }.is_some()
+}
+
+/// Call the listener of `params` like ArkUI does while retrieving data.
+pub(crate) fn report_progress(
+    params: *mut OH_UdmfGetDataParams,
+    progress: c_int,
+    status: Udmf_ListenerStatus,
+    data: *mut OH_UdmfData,
+) {
+    // SAFETY: the caller passes a handle created by the mock, or null.
+    let Some(listener) = unsafe { handle::<GetDataParams, _>(params) }.and_then(|p| p.listener)
+    else {
+        return;
+    };
+    let mut info = ProgressInfo { progress, status };
+    // SAFETY: info lives for the duration of the call and data is a valid handle, or null.
+    unsafe { listener((&raw mut info).cast(), data) };
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_UdmfGetDataParams_Create() -> *mut OH_UdmfGetDataParams {
+    Box::into_raw(Box::<GetDataParams>::default()).cast()
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_UdmfGetDataParams_Destroy(this: *mut OH_UdmfGetDataParams) {
+    // SAFETY: the caller passes a handle created by the mock, or null.
+    if unsafe { is_params(this) } {
+        // SAFETY: the handle was created from a `Box<GetDataParams>` and is destroyed only once.
+        drop(unsafe { Box::from_raw(this.cast::<GetDataParams>()) });
+    }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_UdmfGetDataParams_SetDestUri(
+    params: *mut OH_UdmfGetDataParams,
+    _dest_uri: *const c_char,
+) {
+    // SAFETY: the caller passes a handle created by the mock, or null.
+    debug_assert!(unsafe { is_params(params) });
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_UdmfGetDataParams_SetFileConflictOptions(
+    params: *mut OH_UdmfGetDataParams,
+    _options: Udmf_FileConflictOptions,
+) {
+    // SAFETY: the caller passes a handle created by the mock, or null.
+    debug_assert!(unsafe { is_params(params) });
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_UdmfGetDataParams_SetProgressIndicator(
+    params: *mut OH_UdmfGetDataParams,
+    _progress_indicator: Udmf_ProgressIndicator,
+) {
+    // SAFETY: the caller passes a handle created by the mock, or null.
+    debug_assert!(unsafe { is_params(params) });
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_UdmfGetDataParams_SetDataProgressListener(
+    params: *mut OH_UdmfGetDataParams,
+    listener: OH_Udmf_DataProgressListener,
+) {
+    // SAFETY: the caller passes a handle created by the mock, or null.
+    if let Some(params) = unsafe { handle::<GetDataParams, _>(params) } {
+        params.listener = listener;
+    }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_UdmfProgressInfo_GetProgress(progress_info: *mut OH_Udmf_ProgressInfo) -> c_int {
+    // SAFETY: the caller passes a handle created by the mock, or null.
+    unsafe { handle::<ProgressInfo, _>(progress_info) }.map_or(0, |info| info.progress)
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_UdmfProgressInfo_GetStatus(progress_info: *mut OH_Udmf_ProgressInfo) -> c_int {
+    // SAFETY: the caller passes a handle created by the mock, or null.
+    unsafe { handle::<ProgressInfo, _>(progress_info) }
+        .map_or(Udmf_ListenerStatus::UDMF_INNER_ERROR.0 as c_int, |info| {
+            info.status.0 as c_int
+        })
+}
@@ -0,0 +1,90 @@
+//! An in-memory stand-in for the UDMF and UTD C APIs.
+//!
+//! With the `mock` feature the crate links against an empty `libudmf` and the symbols are
+//! provided by this module instead, so that the safe wrappers can be exercised on hosts without
+//! OpenHarmony. Only the behaviour the wrappers rely on is modelled: data is kept in memory,
+//! nothing leaves the process and the type registry only knows the built-in types. The image
+//! kit's pixel maps are mocked as well, as plain copies of their pixels.
+
+#[cfg(target_env = "ohos")]
+compile_error!(
+    "the `mock` feature replaces the UDMF and UTD C APIs and must not be enabled for OpenHarmony targets"
+);
+
+use std::ffi::{CStr, CString, c_char, c_int, c_uint};
+use std::ptr;
+use udmf_sys::Udmf_ErrCode;
+
+mod data;
+#[cfg(feature = "api-15")]
+pub(crate) mod get_data;
+mod pixel_map;
+mod store;
+mod uds;
+mod utd;
+
+const E_OK: c_int = Udmf_ErrCode::E_OK.0 as c_int;
+const ERR: c_int = Udmf_ErrCode::ERR.0 as c_int;
+const E_INVALID_PARAM: c_int = Udmf_ErrCode::E_INVALID_PARAM.0 as c_int;
+
+/// Borrow a C string argument, or `None` if it is null.
+///
+/// # Safety
+///
+/// `ptr` must be null or point to a valid C string that outlives `'a`.
+unsafe fn c_str<'a>(ptr: *const c_char) -> Option<&'a CStr> {
+    if ptr.is_null() {
+        None
+    } else {
+        // SAFETY: guaranteed by the caller.
+        Some(unsafe { CStr::from_ptr(ptr) })
+    }
+}
+
+/// Borrow the mock object behind an opaque handle, or `None` if it is null.
+///
+/// # Safety
+///
+/// `ptr` must be null or a handle that was created by the mock as a `Box<T>` and not destroyed.
+unsafe fn handle<'a, T, U>(ptr: *mut U) -> Option<&'a mut T> {
+    // SAFETY: guaranteed by the caller.
+    unsafe { ptr.cast::<T>().as_mut() }
+}
+
+/// Write `value` to an out parameter if it is not null.
+///
+/// # Safety
+///
+/// `out` must be null or valid for writes.
+unsafe fn write_out<T>(out: *mut T, value: T) {
+    if !out.is_null() {
+        // SAFETY: guaranteed by the caller.
+        unsafe { out.write(value) };
+    }
+}
+
+/// Strings handed out to C callers. They stay valid until the list is replaced or dropped.
+#[derive(Default)]
+struct StringList {
+    strings: Vec<CString>,
+    ptrs: Vec<*mut c_char>,
+}
+
+impl StringList {
+    /// Replace the list with `strings` and return it in the layout UDMF uses.
+    ///
+    /// # Safety
+    ///
+    /// `count` must be null or valid for writes.
+    unsafe fn replace(&mut self, strings: Vec<CString>, count: *mut c_uint) -> *mut *mut c_char {
+        self.strings = strings;
+        self.ptrs = self.strings.iter().map(|s| s.as_ptr().cast_mut()).collect();
+        // SAFETY: guaranteed by the caller.
+        unsafe { write_out(count, self.ptrs.len() as c_uint) };
+        if self.ptrs.is_empty() {
+            ptr::null_mut()
+        } else {
+            self.ptrs.as_mut_ptr()
+        }
+    }
+}
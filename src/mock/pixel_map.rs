@@ -0,0 +1,339 @@
+//! Mock pixel maps of the image kit, which only hold a copy of their pixels.
+
+use super::{handle, write_out};
+use crate::pixel_map::PixelFormat;
+use crate::pixel_map::ffi::{
+    IMAGE_SUCCESS, Image_ErrorCode, OH_Pixelmap_ImageInfo, OH_Pixelmap_InitializationOptions,
+};
+use ohos_sys_opaque_types::OH_PixelmapNative;
+
+/// `IMAGE_BAD_PARAMETER`.
+const IMAGE_BAD_PARAMETER: Image_ErrorCode = 401;
+
+fn bytes_per_pixel(format: i32) -> Option<u32> {
+    match PixelFormat::from_raw(format) {
+        PixelFormat::Alpha8 => Some(1),
+        PixelFormat::Rgb565 => Some(2),
+        PixelFormat::Rgb888 => Some(3),
+        PixelFormat::Rgba8888 | PixelFormat::Bgra8888 => Some(4),
+        PixelFormat::RgbaF16 => Some(8),
+        _ => None,
+    }
+}
+
+/// The handle behind `OH_PixelmapNative`.
+#[derive(Clone)]
+pub(super) struct MockPixelMap {
+    width: u32,
+    height: u32,
+    pixel_format: i32,
+    pixels: Vec<u8>,
+}
+
+impl MockPixelMap {
+    /// Copy the pixel map behind `ptr`, or `None` if it is null.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be null or a handle created by the mock.
+    pub(super) unsafe fn cloned(ptr: *mut OH_PixelmapNative) -> Option<Self> {
+        // SAFETY: guaranteed by the caller.
+        unsafe { handle::<Self, _>(ptr) }.map(|pixel_map| pixel_map.clone())
+    }
+
+    /// Overwrite the pixel map behind `ptr` with `self`.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be null or a handle created by the mock.
+    pub(super) unsafe fn write_to(&self, ptr: *mut OH_PixelmapNative) {
+        // SAFETY: guaranteed by the caller.
+        if let Some(pixel_map) = unsafe { handle::<Self, _>(ptr) } {
+            pixel_map.clone_from(self);
+        }
+    }
+}
+
+#[derive(Default)]
+struct InitializationOptions {
+    width: u32,
+    height: u32,
+    pixel_format: i32,
+    src_pixel_format: i32,
+}
+
+#[derive(Default)]
+struct ImageInfo {
+    width: u32,
+    height: u32,
+    row_stride: u32,
+    pixel_format: i32,
+}
+
+/// Create a default `T` as an opaque handle in `out`.
+///
+/// # Safety
+///
+/// `out` must be null or valid for writes.
+unsafe fn create<T: Default, U>(out: *mut *mut U) -> Image_ErrorCode {
+    if out.is_null() {
+        return IMAGE_BAD_PARAMETER;
+    }
+    // SAFETY: guaranteed by the caller.
+    unsafe { out.write(Box::into_raw(Box::<T>::default()).cast()) };
+    IMAGE_SUCCESS
+}
+
+/// Release a handle created by [`create`].
+///
+/// # Safety
+///
+/// `ptr` must be null or a handle created as a `Box<T>` that is released only once.
+unsafe fn release<T, U>(ptr: *mut U) -> Image_ErrorCode {
+    if ptr.is_null() {
+        return IMAGE_BAD_PARAMETER;
+    }
+    // SAFETY: guaranteed by the caller.
+    drop(unsafe { Box::from_raw(ptr.cast::<T>()) });
+    IMAGE_SUCCESS
+}
+
+/// Apply `f` to the mock object behind `ptr`.
+///
+/// # Safety
+///
+/// `ptr` must be null or a handle created by the mock as a `Box<T>`.
+unsafe fn with<T, U>(ptr: *mut U, f: impl FnOnce(&mut T)) -> Image_ErrorCode {
+    // SAFETY: guaranteed by the caller.
+    match unsafe { handle::<T, _>(ptr) } {
+        Some(value) => {
+            f(value);
+            IMAGE_SUCCESS
+        }
+        None => IMAGE_BAD_PARAMETER,
+    }
+}
+
+/// Read a field of the mock object behind `ptr` into `out`.
+///
+/// # Safety
+///
+/// `ptr` must be null or a handle created by the mock as a `Box<T>`, `out` null or valid for
+/// writes.
+unsafe fn get<T, U, V>(ptr: *mut U, out: *mut V, f: impl FnOnce(&T) -> V) -> Image_ErrorCode {
+    if out.is_null() {
+        return IMAGE_BAD_PARAMETER;
+    }
+    // SAFETY: guaranteed by the caller.
+    unsafe { with(ptr, |value: &mut T| write_out(out, f(value))) }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_PixelmapInitializationOptions_Create(
+    options: *mut *mut OH_Pixelmap_InitializationOptions,
+) -> Image_ErrorCode {
+    // SAFETY: the caller passes a valid out parameter, or null.
+    unsafe { create::<InitializationOptions, _>(options) }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_PixelmapInitializationOptions_SetWidth(
+    options: *mut OH_Pixelmap_InitializationOptions,
+    width: u32,
+) -> Image_ErrorCode {
+    // SAFETY: the caller passes a handle created by the mock, or null.
+    unsafe {
+        with(options, |options: &mut InitializationOptions| {
+            options.width = width
+        })
+    }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_PixelmapInitializationOptions_SetHeight(
+    options: *mut OH_Pixelmap_InitializationOptions,
+    height: u32,
+) -> Image_ErrorCode {
+    // SAFETY: the caller passes a handle created by the mock, or null.
+    unsafe {
+        with(options, |options: &mut InitializationOptions| {
+            options.height = height
+        })
+    }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_PixelmapInitializationOptions_SetPixelFormat(
+    options: *mut OH_Pixelmap_InitializationOptions,
+    pixel_format: i32,
+) -> Image_ErrorCode {
+    // SAFETY: the caller passes a handle created by the mock, or null.
+    unsafe {
+        with(options, |options: &mut InitializationOptions| {
+            options.pixel_format = pixel_format
+        })
+    }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_PixelmapInitializationOptions_SetSrcPixelFormat(
+    options: *mut OH_Pixelmap_InitializationOptions,
+    src_pixel_format: i32,
+) -> Image_ErrorCode {
+    // SAFETY: the caller passes a handle created by the mock, or null.
+    unsafe {
+        with(options, |options: &mut InitializationOptions| {
+            options.src_pixel_format = src_pixel_format
+        })
+    }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_PixelmapInitializationOptions_Release(
+    options: *mut OH_Pixelmap_InitializationOptions,
+) -> Image_ErrorCode {
+    // SAFETY: the caller passes a handle created by the mock, or null.
+    unsafe { release::<InitializationOptions, _>(options) }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_PixelmapImageInfo_Create(
+    info: *mut *mut OH_Pixelmap_ImageInfo,
+) -> Image_ErrorCode {
+    // SAFETY: the caller passes a valid out parameter, or null.
+    unsafe { create::<ImageInfo, _>(info) }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_PixelmapImageInfo_GetWidth(
+    info: *mut OH_Pixelmap_ImageInfo,
+    width: *mut u32,
+) -> Image_ErrorCode {
+    // SAFETY: the caller passes a handle created by the mock and an out parameter, or nulls.
+    unsafe { get(info, width, |info: &ImageInfo| info.width) }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_PixelmapImageInfo_GetHeight(
+    info: *mut OH_Pixelmap_ImageInfo,
+    height: *mut u32,
+) -> Image_ErrorCode {
+    // SAFETY: the caller passes a handle created by the mock and an out parameter, or nulls.
+    unsafe { get(info, height, |info: &ImageInfo| info.height) }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_PixelmapImageInfo_GetRowStride(
+    info: *mut OH_Pixelmap_ImageInfo,
+    row_stride: *mut u32,
+) -> Image_ErrorCode {
+    // SAFETY: the caller passes a handle created by the mock and an out parameter, or nulls.
+    unsafe { get(info, row_stride, |info: &ImageInfo| info.row_stride) }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_PixelmapImageInfo_GetPixelFormat(
+    info: *mut OH_Pixelmap_ImageInfo,
+    pixel_format: *mut i32,
+) -> Image_ErrorCode {
+    // SAFETY: the caller passes a handle created by the mock and an out parameter, or nulls.
+    unsafe { get(info, pixel_format, |info: &ImageInfo| info.pixel_format) }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_PixelmapImageInfo_Release(info: *mut OH_Pixelmap_ImageInfo) -> Image_ErrorCode {
+    // SAFETY: the caller passes a handle created by the mock, or null.
+    unsafe { release::<ImageInfo, _>(info) }
+}
+
+/// The mock does not convert between formats, so the source and target format must match.
+#[unsafe(no_mangle)]
+extern "C" fn OH_PixelmapNative_CreatePixelmap(
+    data: *mut u8,
+    data_length: usize,
+    options: *mut OH_Pixelmap_InitializationOptions,
+    pixel_map: *mut *mut OH_PixelmapNative,
+) -> Image_ErrorCode {
+    // SAFETY: the caller passes a handle created by the mock, or null.
+    let Some(options) = (unsafe { handle::<InitializationOptions, _>(options) }) else {
+        return IMAGE_BAD_PARAMETER;
+    };
+    let Some(bytes_per_pixel) = bytes_per_pixel(options.pixel_format) else {
+        return IMAGE_BAD_PARAMETER;
+    };
+    let len = options.width as usize * options.height as usize * bytes_per_pixel as usize;
+    if data.is_null()
+        || pixel_map.is_null()
+        || len == 0
+        || data_length < len
+        || options.src_pixel_format != options.pixel_format
+    {
+        return IMAGE_BAD_PARAMETER;
+    }
+    // SAFETY: the caller passes data valid for data_length bytes, which is at least len.
+    let pixels = unsafe { std::slice::from_raw_parts(data, len) }.to_vec();
+    let mock = MockPixelMap {
+        width: options.width,
+        height: options.height,
+        pixel_format: options.pixel_format,
+        pixels,
+    };
+    // SAFETY: pixel_map is a valid out parameter.
+    unsafe { pixel_map.write(Box::into_raw(Box::new(mock)).cast()) };
+    IMAGE_SUCCESS
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_PixelmapNative_ReadPixels(
+    pixel_map: *mut OH_PixelmapNative,
+    destination: *mut u8,
+    buffer_size: *mut usize,
+) -> Image_ErrorCode {
+    // SAFETY: the caller passes a handle created by the mock, or null.
+    let Some(pixel_map) = (unsafe { handle::<MockPixelMap, _>(pixel_map) }) else {
+        return IMAGE_BAD_PARAMETER;
+    };
+    if destination.is_null() || buffer_size.is_null() {
+        return IMAGE_BAD_PARAMETER;
+    }
+    // SAFETY: buffer_size is a valid pointer.
+    let size = unsafe { buffer_size.read() };
+    let len = pixel_map.pixels.len();
+    if size < len {
+        return IMAGE_BAD_PARAMETER;
+    }
+    // SAFETY: destination is valid for writes of size bytes, which is at least len.
+    unsafe {
+        std::ptr::copy_nonoverlapping(pixel_map.pixels.as_ptr(), destination, len);
+        buffer_size.write(len);
+    }
+    IMAGE_SUCCESS
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_PixelmapNative_GetImageInfo(
+    pixel_map: *mut OH_PixelmapNative,
+    image_info: *mut OH_Pixelmap_ImageInfo,
+) -> Image_ErrorCode {
+    // SAFETY: the caller passes a handle created by the mock, or null.
+    let Some(pixel_map) = (unsafe { handle::<MockPixelMap, _>(pixel_map) }) else {
+        return IMAGE_BAD_PARAMETER;
+    };
+    // SAFETY: the caller passes a handle created by the mock, or null.
+    unsafe {
+        with(image_info, |info: &mut ImageInfo| {
+            *info = ImageInfo {
+                width: pixel_map.width,
+                height: pixel_map.height,
+                row_stride: pixel_map.pixels.len() as u32 / pixel_map.height.max(1),
+                pixel_format: pixel_map.pixel_format,
+            }
+        })
+    }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_PixelmapNative_Release(pixel_map: *mut OH_PixelmapNative) -> Image_ErrorCode {
+    // SAFETY: the caller passes a handle created by the mock, or null.
+    unsafe { release::<MockPixelMap, _>(pixel_map) }
+}
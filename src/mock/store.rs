@@ -0,0 +1,380 @@
+//! Mock UDMF database. Stored data lives in a process-wide table.
+
+use super::data::{Data, SharedProperties, SharedRecord, lock, snapshot};
+use super::{E_INVALID_PARAM, E_OK, ERR, c_str, handle};
+use ohos_sys_opaque_types::OH_UdmfData;
+use std::ffi::{CString, c_char, c_int, c_uint};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use udmf_sys::data_management_framework::*;
+
+struct Stored {
+    key: CString,
+    intention: Udmf_Intention,
+    records: Vec<SharedRecord>,
+    properties: SharedProperties,
+}
+
+static STORE: Mutex<Vec<Stored>> = Mutex::new(Vec::new());
+static NEXT_KEY: AtomicU64 = AtomicU64::new(1);
+
+fn intention_name(intention: Udmf_Intention) -> Option<&'static str> {
+    const NAMES: [&str; 6] = [
+        "Drag",
+        "Pasteboard",
+        "DataHub",
+        "SystemShare",
+        "Picker",
+        "Menu",
+    ];
+    NAMES.get(intention.0 as usize).copied()
+}
+
+/// Copy the records and properties of `data` so later changes to it are not visible.
+fn copy_data(data: &Data) -> (Vec<SharedRecord>, SharedProperties) {
+    let records = data.records().iter().map(snapshot).collect();
+    let properties = lock(&data.properties).clone();
+    (records, SharedProperties::new(Mutex::new(properties)))
+}
+
+/// Copy `stored` into a new data handle.
+fn load(stored: &Stored) -> Data {
+    let records = stored.records.iter().map(snapshot).collect();
+    let properties = lock(&stored.properties).clone();
+    Data::new(records, SharedProperties::new(Mutex::new(properties)))
+}
+
+/// Store a copy of `data` and write its key into `key`.
+///
+/// # Safety
+///
+/// `data` must be null or a handle created by the mock and `key` must be null or valid for
+/// writes of `key_len` bytes.
+unsafe fn set(
+    intention: Udmf_Intention,
+    data: *mut OH_UdmfData,
+    key: *mut c_char,
+    key_len: c_uint,
+) -> c_int {
+    // SAFETY: guaranteed by the caller.
+    let Some(data) = (unsafe { handle::<Data, _>(data) }) else {
+        return E_INVALID_PARAM;
+    };
+    let Some(name) = intention_name(intention) else {
+        return E_INVALID_PARAM;
+    };
+    let id = NEXT_KEY.fetch_add(1, Ordering::Relaxed);
+    let new_key = CString::new(format!("udmf://{name}/mock/{id}")).expect("key has no NUL");
+    let bytes = new_key.as_bytes_with_nul();
+    if key.is_null() || bytes.len() > key_len as usize {
+        return E_INVALID_PARAM;
+    }
+    // SAFETY: key is valid for writes of key_len bytes, which is enough for the new key.
+    unsafe { std::ptr::copy_nonoverlapping(bytes.as_ptr().cast(), key, bytes.len()) };
+    let (records, properties) = copy_data(data);
+    lock(&STORE).push(Stored {
+        key: new_key,
+        intention,
+        records,
+        properties,
+    });
+    E_OK
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_Udmf_SetUnifiedData(
+    intention: Udmf_Intention,
+    data: *mut OH_UdmfData,
+    key: *mut c_char,
+    key_len: c_uint,
+) -> c_int {
+    // SAFETY: the caller passes a handle created by the mock and a buffer of key_len bytes.
+    unsafe { set(intention, data, key, key_len) }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_Udmf_GetUnifiedData(
+    key: *const c_char,
+    intention: Udmf_Intention,
+    data: *mut OH_UdmfData,
+) -> c_int {
+    // SAFETY: the caller passes a C string and a handle created by the mock, or nulls.
+    let (Some(key), Some(data)) = (unsafe { c_str(key) }, unsafe { handle::<Data, _>(data) })
+    else {
+        return E_INVALID_PARAM;
+    };
+    let store = lock(&STORE);
+    let Some(stored) = store
+        .iter()
+        .find(|s| s.key.as_c_str() == key && s.intention == intention)
+    else {
+        return ERR;
+    };
+    *data = load(stored);
+    E_OK
+}
+
+#[cfg(feature = "api-20")]
+mod options {
+    use super::super::write_out;
+    use super::*;
+    use std::ptr;
+
+    /// The handle behind `OH_UdmfOptions`.
+    #[derive(Default)]
+    struct Options {
+        key: CString,
+        intention: Option<Udmf_Intention>,
+        visibility: Option<Udmf_Visibility>,
+    }
+
+    impl Options {
+        /// Whether `stored` is selected by these options.
+        fn matches(&self, stored: &Stored) -> bool {
+            (self.key.is_empty() || self.key == stored.key)
+                && self.intention.is_none_or(|i| i == stored.intention)
+        }
+    }
+
+    /// Borrow the options behind `ptr`.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be null or a handle created by the mock.
+    unsafe fn borrow_options<'a>(ptr: *mut OH_UdmfOptions) -> Option<&'a mut Options> {
+        // SAFETY: guaranteed by the caller.
+        unsafe { handle::<Options, _>(ptr) }
+    }
+
+    /// Hand out `data` as an array that is released by `OH_Udmf_DestroyDataArray`.
+    ///
+    /// # Safety
+    ///
+    /// `data_array` and `data_size` must be null or valid for writes.
+    unsafe fn write_array(
+        data: Vec<Data>,
+        data_array: *mut *mut OH_UdmfData,
+        data_size: *mut c_uint,
+    ) {
+        let size = data.len() as c_uint;
+        let array = if data.is_empty() {
+            ptr::null_mut()
+        } else {
+            Box::into_raw(data.into_boxed_slice()).cast::<OH_UdmfData>()
+        };
+        // SAFETY: guaranteed by the caller.
+        unsafe {
+            write_out(data_array, array);
+            write_out(data_size, size);
+        }
+    }
+
+    #[unsafe(no_mangle)]
+    extern "C" fn OH_UdmfOptions_Create() -> *mut OH_UdmfOptions {
+        Box::into_raw(Box::new(Options::default())).cast()
+    }
+
+    #[unsafe(no_mangle)]
+    extern "C" fn OH_UdmfOptions_Destroy(this: *mut OH_UdmfOptions) {
+        if !this.is_null() {
+            // SAFETY: the handle was created from a `Box<Options>` and is destroyed only once.
+            drop(unsafe { Box::from_raw(this.cast::<Options>()) });
+        }
+    }
+
+    #[unsafe(no_mangle)]
+    extern "C" fn OH_UdmfOptions_GetKey(this: *mut OH_UdmfOptions) -> *const c_char {
+        // SAFETY: the caller passes a handle created by the mock, or null.
+        match unsafe { borrow_options(this) } {
+            Some(options) if !options.key.is_empty() => options.key.as_ptr(),
+            _ => ptr::null(),
+        }
+    }
+
+    #[unsafe(no_mangle)]
+    extern "C" fn OH_UdmfOptions_SetKey(this: *mut OH_UdmfOptions, key: *const c_char) -> c_int {
+        // SAFETY: the caller passes a handle created by the mock and a C string, or nulls.
+        match unsafe { (borrow_options(this), c_str(key)) } {
+            (Some(options), Some(key)) => {
+                options.key = key.to_owned();
+                E_OK
+            }
+            _ => E_INVALID_PARAM,
+        }
+    }
+
+    #[unsafe(no_mangle)]
+    extern "C" fn OH_UdmfOptions_GetIntention(this: *mut OH_UdmfOptions) -> Udmf_Intention {
+        // SAFETY: the caller passes a handle created by the mock, or null.
+        unsafe { borrow_options(this) }
+            .and_then(|options| options.intention)
+            .unwrap_or(Udmf_Intention(c_uint::MAX))
+    }
+
+    #[unsafe(no_mangle)]
+    extern "C" fn OH_UdmfOptions_SetIntention(
+        this: *mut OH_UdmfOptions,
+        intention: Udmf_Intention,
+    ) -> c_int {
+        // SAFETY: the caller passes a handle created by the mock, or null.
+        match unsafe { borrow_options(this) } {
+            Some(options) if intention_name(intention).is_some() => {
+                options.intention = Some(intention);
+                E_OK
+            }
+            _ => E_INVALID_PARAM,
+        }
+    }
+
+    #[unsafe(no_mangle)]
+    extern "C" fn OH_UdmfOptions_Reset(this: *mut OH_UdmfOptions) -> c_int {
+        // SAFETY: the caller passes a handle created by the mock, or null.
+        match unsafe { borrow_options(this) } {
+            Some(options) => {
+                *options = Options::default();
+                E_OK
+            }
+            None => E_INVALID_PARAM,
+        }
+    }
+
+    #[unsafe(no_mangle)]
+    extern "C" fn OH_UdmfOptions_GetVisibility(this: *mut OH_UdmfOptions) -> Udmf_Visibility {
+        // SAFETY: the caller passes a handle created by the mock, or null.
+        unsafe { borrow_options(this) }
+            .and_then(|options| options.visibility)
+            .unwrap_or(Udmf_Visibility::UDMF_ALL)
+    }
+
+    #[unsafe(no_mangle)]
+    extern "C" fn OH_UdmfOptions_SetVisibility(
+        this: *mut OH_UdmfOptions,
+        visibility: Udmf_Visibility,
+    ) -> c_int {
+        // SAFETY: the caller passes a handle created by the mock, or null.
+        match unsafe { borrow_options(this) } {
+            Some(options)
+                if visibility == Udmf_Visibility::UDMF_ALL
+                    || visibility == Udmf_Visibility::UDMF_OWN_PROCESS =>
+            {
+                options.visibility = Some(visibility);
+                E_OK
+            }
+            _ => E_INVALID_PARAM,
+        }
+    }
+
+    #[unsafe(no_mangle)]
+    extern "C" fn OH_Udmf_SetUnifiedDataByOptions(
+        options: *mut OH_UdmfOptions,
+        data: *mut OH_UdmfData,
+        key: *mut c_char,
+        key_len: c_uint,
+    ) -> c_int {
+        // SAFETY: the caller passes a handle created by the mock, or null.
+        let Some(intention) =
+            unsafe { borrow_options(options) }.and_then(|options| options.intention)
+        else {
+            return E_INVALID_PARAM;
+        };
+        // SAFETY: the caller passes a handle created by the mock and a buffer of key_len bytes.
+        unsafe { set(intention, data, key, key_len) }
+    }
+
+    #[unsafe(no_mangle)]
+    extern "C" fn OH_Udmf_GetUnifiedDataByOptions(
+        options: *mut OH_UdmfOptions,
+        data_array: *mut *mut OH_UdmfData,
+        data_size: *mut c_uint,
+    ) -> c_int {
+        // SAFETY: the caller passes a handle created by the mock, or null.
+        let Some(options) = (unsafe { borrow_options(options) }) else {
+            return E_INVALID_PARAM;
+        };
+        let data = lock(&STORE)
+            .iter()
+            .filter(|stored| options.matches(stored))
+            .map(load)
+            .collect();
+        // SAFETY: the caller passes valid out parameters or nulls.
+        unsafe { write_array(data, data_array, data_size) };
+        E_OK
+    }
+
+    #[unsafe(no_mangle)]
+    extern "C" fn OH_Udmf_UpdateUnifiedData(
+        options: *mut OH_UdmfOptions,
+        data: *mut OH_UdmfData,
+    ) -> c_int {
+        // SAFETY: the caller passes handles created by the mock, or nulls.
+        let (Some(options), Some(data)) = (unsafe { borrow_options(options) }, unsafe {
+            handle::<Data, _>(data)
+        }) else {
+            return E_INVALID_PARAM;
+        };
+        if options.key.is_empty() {
+            return E_INVALID_PARAM;
+        }
+        let mut store = lock(&STORE);
+        let Some(stored) = store.iter_mut().find(|stored| options.matches(stored)) else {
+            return ERR;
+        };
+        (stored.records, stored.properties) = copy_data(data);
+        E_OK
+    }
+
+    #[unsafe(no_mangle)]
+    extern "C" fn OH_Udmf_DeleteUnifiedData(
+        options: *mut OH_UdmfOptions,
+        data_array: *mut *mut OH_UdmfData,
+        data_size: *mut c_uint,
+    ) -> c_int {
+        // SAFETY: the caller passes a handle created by the mock, or null.
+        let Some(options) = (unsafe { borrow_options(options) }) else {
+            return E_INVALID_PARAM;
+        };
+        if options.key.is_empty() && options.intention.is_none() {
+            return E_INVALID_PARAM;
+        }
+        let mut store = lock(&STORE);
+        let (deleted, kept): (Vec<_>, Vec<_>) =
+            store.drain(..).partition(|stored| options.matches(stored));
+        *store = kept;
+        drop(store);
+        let data = deleted.iter().map(load).collect();
+        // SAFETY: the caller passes valid out parameters or nulls.
+        unsafe { write_array(data, data_array, data_size) };
+        E_OK
+    }
+
+    #[unsafe(no_mangle)]
+    extern "C" fn OH_Udmf_GetDataElementAt(
+        data_array: *mut *mut OH_UdmfData,
+        index: c_uint,
+    ) -> *mut OH_UdmfData {
+        if data_array.is_null() {
+            return ptr::null_mut();
+        }
+        // SAFETY: data_array points to an array handed out by `write_array`, or null.
+        let array = unsafe { data_array.read() };
+        if array.is_null() {
+            return ptr::null_mut();
+        }
+        // SAFETY: the caller passes an index within the array handed out by `write_array`.
+        unsafe { array.cast::<Data>().add(index as usize) }.cast()
+    }
+
+    #[unsafe(no_mangle)]
+    extern "C" fn OH_Udmf_DestroyDataArray(data_array: *mut *mut OH_UdmfData, data_size: c_uint) {
+        if data_array.is_null() {
+            return;
+        }
+        // SAFETY: data_array points to an array handed out by `write_array`, or null.
+        let array = unsafe { data_array.replace(ptr::null_mut()) };
+        if !array.is_null() {
+            let slice = ptr::slice_from_raw_parts_mut(array.cast::<Data>(), data_size as usize);
+            // SAFETY: the array was created from a `Box<[Data]>` of data_size elements.
+            drop(unsafe { Box::from_raw(slice) });
+        }
+    }
+}
@@ -0,0 +1,479 @@
+//! Mock UDS objects. All kinds share one representation with named fields.
+
+use super::pixel_map::MockPixelMap;
+use super::{E_INVALID_PARAM, E_OK, c_str, handle, write_out};
+use ohos_sys_opaque_types::*;
+use std::collections::HashMap;
+use std::ffi::{CStr, CString, c_char, c_int, c_uchar, c_uint};
+use std::ptr;
+use udmf_sys::meta::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Kind {
+    PlainText,
+    Hyperlink,
+    Html,
+    AppItem,
+    FileUri,
+    PixelMap,
+    ArrayBuffer,
+    ContentForm,
+}
+
+impl Kind {
+    /// The type id reported by `GetType` and used when the object is added to a record.
+    ///
+    /// Array buffers are stored under a caller provided type and have no type of their own.
+    pub(super) fn type_id(self) -> &'static CStr {
+        match self {
+            Kind::PlainText => UDMF_META_PLAIN_TEXT,
+            Kind::Hyperlink => UDMF_META_HYPERLINK,
+            Kind::Html => UDMF_META_HTML,
+            Kind::AppItem => UDMF_META_OPENHARMONY_APP_ITEM,
+            Kind::FileUri => UDMF_META_GENERAL_FILE_URI,
+            Kind::PixelMap => UDMF_META_OPENHARMONY_PIXEL_MAP,
+            Kind::ArrayBuffer => c"",
+            Kind::ContentForm => UDMF_METE_GENERAL_CONTENT_FORM,
+        }
+    }
+}
+
+/// The field that holds the bytes of an array buffer.
+pub(super) const ARRAY_BUFFER_DATA: &str = "data";
+
+#[derive(Clone)]
+pub(super) struct Uds {
+    pub(super) kind: Kind,
+    strings: HashMap<&'static str, CString>,
+    bytes: HashMap<&'static str, Vec<u8>>,
+    pixel_map: Option<MockPixelMap>,
+}
+
+impl Uds {
+    pub(super) fn new(kind: Kind) -> Self {
+        Self {
+            kind,
+            strings: HashMap::new(),
+            bytes: HashMap::new(),
+            pixel_map: None,
+        }
+    }
+
+    pub(super) fn array_buffer(data: Vec<u8>) -> Self {
+        let mut uds = Self::new(Kind::ArrayBuffer);
+        uds.bytes.insert(ARRAY_BUFFER_DATA, data);
+        uds
+    }
+
+    pub(super) fn bytes(&self, field: &str) -> &[u8] {
+        self.bytes.get(field).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Unset fields read as an empty string.
+    fn string(&self, field: &str) -> *const c_char {
+        self.strings
+            .get(field)
+            .map_or(c"".as_ptr(), |value| value.as_ptr())
+    }
+}
+
+/// Borrow the UDS behind `ptr` if it is of `kind`.
+///
+/// # Safety
+///
+/// `ptr` must be null or a UDS handle created by the mock.
+pub(super) unsafe fn uds<'a, T>(ptr: *mut T, kind: Kind) -> Option<&'a mut Uds> {
+    // SAFETY: guaranteed by the caller.
+    unsafe { handle::<Uds, T>(ptr) }.filter(|uds| uds.kind == kind)
+}
+
+/// Return the bytes of `field` through the out parameters.
+///
+/// # Safety
+///
+/// `data` and `len` must be null or valid for writes.
+unsafe fn get_bytes(
+    uds: Option<&mut Uds>,
+    field: &'static str,
+    data: *mut *mut c_uchar,
+    len: *mut c_uint,
+) -> c_int {
+    let Some(uds) = uds else {
+        return E_INVALID_PARAM;
+    };
+    let bytes = uds.bytes.entry(field).or_default();
+    // SAFETY: guaranteed by the caller.
+    unsafe {
+        write_out(data, bytes.as_mut_ptr());
+        write_out(len, bytes.len() as c_uint);
+    }
+    E_OK
+}
+
+/// Copy `len` bytes from `data` into `field`.
+///
+/// Like UDMF, empty buffers are rejected.
+///
+/// # Safety
+///
+/// `data` must be null or valid for reads of `len` bytes.
+unsafe fn set_bytes(
+    uds: Option<&mut Uds>,
+    field: &'static str,
+    data: *const c_uchar,
+    len: c_uint,
+) -> c_int {
+    let Some(uds) = uds else {
+        return E_INVALID_PARAM;
+    };
+    if data.is_null() || len == 0 {
+        return E_INVALID_PARAM;
+    }
+    // SAFETY: guaranteed by the caller.
+    let bytes = unsafe { std::slice::from_raw_parts(data, len as usize) }.to_vec();
+    uds.bytes.insert(field, bytes);
+    E_OK
+}
+
+macro_rules! uds_object {
+    ($raw:ty, $kind:ident, $create:ident, $destroy:ident) => {
+        #[unsafe(no_mangle)]
+        extern "C" fn $create() -> *mut $raw {
+            Box::into_raw(Box::new(Uds::new(Kind::$kind))).cast()
+        }
+
+        #[unsafe(no_mangle)]
+        extern "C" fn $destroy(this: *mut $raw) {
+            // SAFETY: the caller passes a handle created by the mock, or null.
+            if unsafe { uds(this, Kind::$kind) }.is_some() {
+                // SAFETY: the handle was created from a `Box<Uds>` and is destroyed only once.
+                drop(unsafe { Box::from_raw(this.cast::<Uds>()) });
+            }
+        }
+    };
+    ($raw:ty, $kind:ident, $create:ident, $destroy:ident, $get_type:ident) => {
+        uds_object!($raw, $kind, $create, $destroy);
+
+        #[unsafe(no_mangle)]
+        extern "C" fn $get_type(this: *mut $raw) -> *const c_char {
+            // SAFETY: the caller passes a handle created by the mock, or null.
+            match unsafe { uds(this, Kind::$kind) } {
+                Some(uds) => uds.kind.type_id().as_ptr(),
+                None => ptr::null(),
+            }
+        }
+    };
+}
+
+macro_rules! string_field {
+    ($raw:ty, $kind:ident, $field:literal, $get:ident, $set:ident) => {
+        #[unsafe(no_mangle)]
+        extern "C" fn $get(this: *mut $raw) -> *const c_char {
+            // SAFETY: the caller passes a handle created by the mock, or null.
+            match unsafe { uds(this, Kind::$kind) } {
+                Some(uds) => uds.string($field),
+                None => ptr::null(),
+            }
+        }
+
+        #[unsafe(no_mangle)]
+        extern "C" fn $set(this: *mut $raw, value: *const c_char) -> c_int {
+            // SAFETY: the caller passes a handle created by the mock and a C string, or nulls.
+            match unsafe { (uds(this, Kind::$kind), c_str(value)) } {
+                (Some(uds), Some(value)) => {
+                    uds.strings.insert($field, value.to_owned());
+                    E_OK
+                }
+                _ => E_INVALID_PARAM,
+            }
+        }
+    };
+}
+
+uds_object!(
+    OH_UdsPlainText,
+    PlainText,
+    OH_UdsPlainText_Create,
+    OH_UdsPlainText_Destroy,
+    OH_UdsPlainText_GetType
+);
+string_field!(
+    OH_UdsPlainText,
+    PlainText,
+    "content",
+    OH_UdsPlainText_GetContent,
+    OH_UdsPlainText_SetContent
+);
+string_field!(
+    OH_UdsPlainText,
+    PlainText,
+    "abstract",
+    OH_UdsPlainText_GetAbstract,
+    OH_UdsPlainText_SetAbstract
+);
+
+uds_object!(
+    OH_UdsHyperlink,
+    Hyperlink,
+    OH_UdsHyperlink_Create,
+    OH_UdsHyperlink_Destroy,
+    OH_UdsHyperlink_GetType
+);
+string_field!(
+    OH_UdsHyperlink,
+    Hyperlink,
+    "url",
+    OH_UdsHyperlink_GetUrl,
+    OH_UdsHyperlink_SetUrl
+);
+string_field!(
+    OH_UdsHyperlink,
+    Hyperlink,
+    "description",
+    OH_UdsHyperlink_GetDescription,
+    OH_UdsHyperlink_SetDescription
+);
+
+uds_object!(
+    OH_UdsHtml,
+    Html,
+    OH_UdsHtml_Create,
+    OH_UdsHtml_Destroy,
+    OH_UdsHtml_GetType
+);
+string_field!(
+    OH_UdsHtml,
+    Html,
+    "content",
+    OH_UdsHtml_GetContent,
+    OH_UdsHtml_SetContent
+);
+string_field!(
+    OH_UdsHtml,
+    Html,
+    "plain_content",
+    OH_UdsHtml_GetPlainContent,
+    OH_UdsHtml_SetPlainContent
+);
+
+uds_object!(
+    OH_UdsAppItem,
+    AppItem,
+    OH_UdsAppItem_Create,
+    OH_UdsAppItem_Destroy,
+    OH_UdsAppItem_GetType
+);
+string_field!(
+    OH_UdsAppItem,
+    AppItem,
+    "id",
+    OH_UdsAppItem_GetId,
+    OH_UdsAppItem_SetId
+);
+string_field!(
+    OH_UdsAppItem,
+    AppItem,
+    "name",
+    OH_UdsAppItem_GetName,
+    OH_UdsAppItem_SetName
+);
+string_field!(
+    OH_UdsAppItem,
+    AppItem,
+    "icon_id",
+    OH_UdsAppItem_GetIconId,
+    OH_UdsAppItem_SetIconId
+);
+string_field!(
+    OH_UdsAppItem,
+    AppItem,
+    "label_id",
+    OH_UdsAppItem_GetLabelId,
+    OH_UdsAppItem_SetLabelId
+);
+string_field!(
+    OH_UdsAppItem,
+    AppItem,
+    "bundle_name",
+    OH_UdsAppItem_GetBundleName,
+    OH_UdsAppItem_SetBundleName
+);
+string_field!(
+    OH_UdsAppItem,
+    AppItem,
+    "ability_name",
+    OH_UdsAppItem_GetAbilityName,
+    OH_UdsAppItem_SetAbilityName
+);
+
+uds_object!(
+    OH_UdsFileUri,
+    FileUri,
+    OH_UdsFileUri_Create,
+    OH_UdsFileUri_Destroy,
+    OH_UdsFileUri_GetType
+);
+string_field!(
+    OH_UdsFileUri,
+    FileUri,
+    "file_uri",
+    OH_UdsFileUri_GetFileUri,
+    OH_UdsFileUri_SetFileUri
+);
+string_field!(
+    OH_UdsFileUri,
+    FileUri,
+    "file_type",
+    OH_UdsFileUri_GetFileType,
+    OH_UdsFileUri_SetFileType
+);
+
+uds_object!(
+    OH_UdsPixelMap,
+    PixelMap,
+    OH_UdsPixelMap_Create,
+    OH_UdsPixelMap_Destroy,
+    OH_UdsPixelMap_GetType
+);
+
+/// UDMF copies the pixel map into the object passed by the caller.
+#[unsafe(no_mangle)]
+extern "C" fn OH_UdsPixelMap_GetPixelMap(
+    this: *mut OH_UdsPixelMap,
+    pixel_map: *mut OH_PixelmapNative,
+) {
+    // SAFETY: the caller passes a handle created by the mock, or null.
+    if let Some(stored) =
+        unsafe { uds(this, Kind::PixelMap) }.and_then(|uds| uds.pixel_map.as_ref())
+    {
+        // SAFETY: the caller passes a pixel map created by the mock, or null.
+        unsafe { stored.write_to(pixel_map) };
+    }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_UdsPixelMap_SetPixelMap(
+    this: *mut OH_UdsPixelMap,
+    pixel_map: *mut OH_PixelmapNative,
+) -> c_int {
+    // SAFETY: the caller passes handles created by the mock, or nulls.
+    match unsafe { (uds(this, Kind::PixelMap), MockPixelMap::cloned(pixel_map)) } {
+        (Some(uds), Some(pixel_map)) => {
+            uds.pixel_map = Some(pixel_map);
+            E_OK
+        }
+        _ => E_INVALID_PARAM,
+    }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_UdsArrayBuffer_Create() -> *mut OH_UdsArrayBuffer {
+    Box::into_raw(Box::new(Uds::array_buffer(Vec::new()))).cast()
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_UdsArrayBuffer_Destroy(buffer: *mut OH_UdsArrayBuffer) -> c_int {
+    // SAFETY: the caller passes a handle created by the mock, or null.
+    if unsafe { uds(buffer, Kind::ArrayBuffer) }.is_none() {
+        return E_INVALID_PARAM;
+    }
+    // SAFETY: the handle was created from a `Box<Uds>` and is destroyed only once.
+    drop(unsafe { Box::from_raw(buffer.cast::<Uds>()) });
+    E_OK
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_UdsArrayBuffer_SetData(
+    buffer: *mut OH_UdsArrayBuffer,
+    data: *mut c_uchar,
+    len: c_uint,
+) -> c_int {
+    // SAFETY: the caller passes a handle created by the mock and `len` readable bytes.
+    unsafe { set_bytes(uds(buffer, Kind::ArrayBuffer), ARRAY_BUFFER_DATA, data, len) }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_UdsArrayBuffer_GetData(
+    buffer: *mut OH_UdsArrayBuffer,
+    data: *mut *mut c_uchar,
+    len: *mut c_uint,
+) -> c_int {
+    // SAFETY: the caller passes a handle created by the mock and writable out parameters.
+    unsafe { get_bytes(uds(buffer, Kind::ArrayBuffer), ARRAY_BUFFER_DATA, data, len) }
+}
+
+uds_object!(
+    OH_UdsContentForm,
+    ContentForm,
+    OH_UdsContentForm_Create,
+    OH_UdsContentForm_Destroy,
+    OH_UdsContentForm_GetType
+);
+string_field!(
+    OH_UdsContentForm,
+    ContentForm,
+    "description",
+    OH_UdsContentForm_GetDescription,
+    OH_UdsContentForm_SetDescription
+);
+string_field!(
+    OH_UdsContentForm,
+    ContentForm,
+    "title",
+    OH_UdsContentForm_GetTitle,
+    OH_UdsContentForm_SetTitle
+);
+string_field!(
+    OH_UdsContentForm,
+    ContentForm,
+    "app_name",
+    OH_UdsContentForm_GetAppName,
+    OH_UdsContentForm_SetAppName
+);
+string_field!(
+    OH_UdsContentForm,
+    ContentForm,
+    "link_uri",
+    OH_UdsContentForm_GetLinkUri,
+    OH_UdsContentForm_SetLinkUri
+);
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_UdsContentForm_GetThumbData(
+    this: *mut OH_UdsContentForm,
+    thumb_data: *mut *mut c_uchar,
+    len: *mut c_uint,
+) -> c_int {
+    // SAFETY: the caller passes a handle created by the mock and writable out parameters.
+    unsafe { get_bytes(uds(this, Kind::ContentForm), "thumb_data", thumb_data, len) }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_UdsContentForm_SetThumbData(
+    this: *mut OH_UdsContentForm,
+    thumb_data: *const c_uchar,
+    len: c_uint,
+) -> c_int {
+    // SAFETY: the caller passes a handle created by the mock and `len` readable bytes.
+    unsafe { set_bytes(uds(this, Kind::ContentForm), "thumb_data", thumb_data, len) }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_UdsContentForm_GetAppIcon(
+    this: *mut OH_UdsContentForm,
+    app_icon: *mut *mut c_uchar,
+    len: *mut c_uint,
+) -> c_int {
+    // SAFETY: the caller passes a handle created by the mock and writable out parameters.
+    unsafe { get_bytes(uds(this, Kind::ContentForm), "app_icon", app_icon, len) }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_UdsContentForm_SetAppIcon(
+    this: *mut OH_UdsContentForm,
+    app_icon: *const c_uchar,
+    len: c_uint,
+) -> c_int {
+    // SAFETY: the caller passes a handle created by the mock and `len` readable bytes.
+    unsafe { set_bytes(uds(this, Kind::ContentForm), "app_icon", app_icon, len) }
+}
@@ -0,0 +1,241 @@
+//! Mock uniform type descriptors backed by the built-in copy of the standard type table.
+
+use super::{c_str, handle, write_out};
+use crate::known_types::{KNOWN_TYPES, TypeMetadata};
+use crate::types::UniformDataType;
+use ohos_sys_opaque_types::OH_Utd;
+use std::ffi::{CStr, CString, c_char, c_uint};
+use std::ptr;
+
+fn find(type_id: &CStr) -> Option<&'static TypeMetadata> {
+    KNOWN_TYPES
+        .iter()
+        .find(|metadata| metadata.type_id.to_cstr() == type_id)
+}
+
+/// Whether `src` is `dest` or one of its descendants.
+fn belongs_to(src: &CStr, dest: &CStr) -> bool {
+    UniformDataType::from(src).belongs_to(&UniformDataType::from(dest))
+}
+
+/// The handle behind `OH_Utd`, with the lists handed out by its getters.
+struct Utd {
+    metadata: &'static TypeMetadata,
+    belongs_to: Vec<*const c_char>,
+    extensions: Vec<*const c_char>,
+    mime_types: Vec<*const c_char>,
+}
+
+fn pointers(list: &[&'static CStr]) -> Vec<*const c_char> {
+    list.iter().map(|s| s.as_ptr()).collect()
+}
+
+/// Hand out `list`, which stays owned by the descriptor.
+///
+/// # Safety
+///
+/// `count` must be null or valid for writes.
+unsafe fn borrowed_list(list: &mut [*const c_char], count: *mut c_uint) -> *mut *const c_char {
+    // SAFETY: guaranteed by the caller.
+    unsafe { write_out(count, list.len() as c_uint) };
+    if list.is_empty() {
+        ptr::null_mut()
+    } else {
+        list.as_mut_ptr()
+    }
+}
+
+/// Hand out a copy of `list` that is released by `OH_Utd_DestroyStringList`.
+///
+/// # Safety
+///
+/// `count` must be null or valid for writes.
+unsafe fn owned_list(list: Vec<&CStr>, count: *mut c_uint) -> *mut *const c_char {
+    // SAFETY: guaranteed by the caller.
+    unsafe { write_out(count, list.len() as c_uint) };
+    if list.is_empty() {
+        return ptr::null_mut();
+    }
+    let list: Box<[*const c_char]> = list
+        .into_iter()
+        .map(|s| s.to_owned().into_raw().cast_const())
+        .collect();
+    Box::into_raw(list).cast()
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_Utd_Create(type_id: *const c_char) -> *mut OH_Utd {
+    // SAFETY: the caller passes a C string, or null.
+    let Some(metadata) = unsafe { c_str(type_id) }.and_then(find) else {
+        return ptr::null_mut();
+    };
+    let belongs_to = metadata
+        .belonging_to_types
+        .iter()
+        .map(|parent| parent.to_cstr().as_ptr())
+        .collect();
+    let utd = Utd {
+        metadata,
+        belongs_to,
+        extensions: pointers(metadata.filename_extensions),
+        mime_types: pointers(metadata.mime_types),
+    };
+    Box::into_raw(Box::new(utd)).cast()
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_Utd_Destroy(this: *mut OH_Utd) {
+    if !this.is_null() {
+        // SAFETY: the handle was created from a `Box<Utd>` and is destroyed only once.
+        drop(unsafe { Box::from_raw(this.cast::<Utd>()) });
+    }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_Utd_GetTypeId(this: *mut OH_Utd) -> *const c_char {
+    // SAFETY: the caller passes a handle created by the mock, or null.
+    unsafe { handle::<Utd, _>(this) }
+        .map_or(ptr::null(), |utd| utd.metadata.type_id.to_cstr().as_ptr())
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_Utd_GetDescription(this: *mut OH_Utd) -> *const c_char {
+    // SAFETY: the caller passes a handle created by the mock, or null.
+    unsafe { handle::<Utd, _>(this) }.map_or(ptr::null(), |utd| utd.metadata.description.as_ptr())
+}
+
+/// The built-in types have no reference URL.
+#[unsafe(no_mangle)]
+extern "C" fn OH_Utd_GetReferenceUrl(_this: *mut OH_Utd) -> *const c_char {
+    ptr::null()
+}
+
+/// The built-in types have no icon.
+#[unsafe(no_mangle)]
+extern "C" fn OH_Utd_GetIconFile(_this: *mut OH_Utd) -> *const c_char {
+    ptr::null()
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_Utd_GetBelongingToTypes(
+    this: *mut OH_Utd,
+    count: *mut c_uint,
+) -> *mut *const c_char {
+    // SAFETY: the caller passes a handle created by the mock and a count out parameter, or nulls.
+    unsafe { handle::<Utd, _>(this) }.map_or(ptr::null_mut(), |utd| unsafe {
+        borrowed_list(&mut utd.belongs_to, count)
+    })
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_Utd_GetFilenameExtensions(
+    this: *mut OH_Utd,
+    count: *mut c_uint,
+) -> *mut *const c_char {
+    // SAFETY: the caller passes a handle created by the mock and a count out parameter, or nulls.
+    unsafe { handle::<Utd, _>(this) }.map_or(ptr::null_mut(), |utd| unsafe {
+        borrowed_list(&mut utd.extensions, count)
+    })
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_Utd_GetMimeTypes(this: *mut OH_Utd, count: *mut c_uint) -> *mut *const c_char {
+    // SAFETY: the caller passes a handle created by the mock and a count out parameter, or nulls.
+    unsafe { handle::<Utd, _>(this) }.map_or(ptr::null_mut(), |utd| unsafe {
+        borrowed_list(&mut utd.mime_types, count)
+    })
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_Utd_GetTypesByFilenameExtension(
+    extension: *const c_char,
+    count: *mut c_uint,
+) -> *mut *const c_char {
+    // SAFETY: the caller passes a C string, or null.
+    let Some(extension) = (unsafe { c_str(extension) }) else {
+        return ptr::null_mut();
+    };
+    let extension = extension.to_bytes().to_ascii_lowercase();
+    let types = KNOWN_TYPES
+        .iter()
+        .filter(|metadata| {
+            metadata
+                .filename_extensions
+                .iter()
+                .any(|e| e.to_bytes() == extension)
+        })
+        .map(|metadata| metadata.type_id.to_cstr())
+        .collect();
+    // SAFETY: count is a valid out parameter or null.
+    unsafe { owned_list(types, count) }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_Utd_GetTypesByMimeType(
+    mime_type: *const c_char,
+    count: *mut c_uint,
+) -> *mut *const c_char {
+    // SAFETY: the caller passes a C string, or null.
+    let Some(mime_type) = (unsafe { c_str(mime_type) }) else {
+        return ptr::null_mut();
+    };
+    let mime_type = mime_type.to_bytes().to_ascii_lowercase();
+    let types = KNOWN_TYPES
+        .iter()
+        .filter(|metadata| {
+            metadata
+                .mime_types
+                .iter()
+                .any(|m| m.to_bytes() == mime_type)
+        })
+        .map(|metadata| metadata.type_id.to_cstr())
+        .collect();
+    // SAFETY: count is a valid out parameter or null.
+    unsafe { owned_list(types, count) }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_Utd_DestroyStringList(list: *mut *const c_char, count: c_uint) {
+    if list.is_null() {
+        return;
+    }
+    let list = ptr::slice_from_raw_parts_mut(list, count as usize);
+    // SAFETY: the list was created from a `Box<[*const c_char]>` by `owned_list`.
+    for s in unsafe { Box::from_raw(list) } {
+        // SAFETY: every string was created by `CString::into_raw`.
+        drop(unsafe { CString::from_raw(s.cast_mut()) });
+    }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_Utd_BelongsTo(src_type_id: *const c_char, dest_type_id: *const c_char) -> bool {
+    // SAFETY: the caller passes C strings, or nulls.
+    match unsafe { (c_str(src_type_id), c_str(dest_type_id)) } {
+        (Some(src), Some(dest)) => belongs_to(src, dest),
+        _ => false,
+    }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_Utd_IsLower(src_type_id: *const c_char, dest_type_id: *const c_char) -> bool {
+    // SAFETY: the caller passes C strings, or nulls.
+    match unsafe { (c_str(src_type_id), c_str(dest_type_id)) } {
+        (Some(src), Some(dest)) => src != dest && belongs_to(src, dest),
+        _ => false,
+    }
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_Utd_IsHigher(src_type_id: *const c_char, dest_type_id: *const c_char) -> bool {
+    OH_Utd_IsLower(dest_type_id, src_type_id)
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn OH_Utd_Equals(utd1: *mut OH_Utd, utd2: *mut OH_Utd) -> bool {
+    // SAFETY: the caller passes handles created by the mock, or nulls. The borrows end before
+    // the next one starts, so both arguments may be the same handle.
+    let id1 = unsafe { handle::<Utd, _>(utd1) }.map(|utd| utd.metadata.id);
+    // SAFETY: as above.
+    let id2 = unsafe { handle::<Utd, _>(utd2) }.map(|utd| utd.metadata.id);
+    id1.is_some() && id1 == id2
+}
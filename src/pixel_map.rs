@@ -0,0 +1,392 @@
+//! Safe handles for native pixel maps of the image kit.
+//!
+//! A [`PixelMap`](crate::PixelMap) UDS carries an `OH_PixelmapNative`, e.g. the preview image of
+//! a drag. [`NativePixelMap`] wraps such a handle and tracks whether it has to be released.
+
+use crate::error::{Result, UdmfError};
+use ohos_sys_opaque_types::OH_PixelmapNative;
+use std::marker::PhantomData;
+use std::ptr::NonNull;
+
+/// The parts of the image kit's `pixelmap_native.h` used by this crate.
+#[allow(non_camel_case_types)]
+pub(crate) mod ffi {
+    use ohos_sys_opaque_types::OH_PixelmapNative;
+
+    /// `Image_ErrorCode`, which is `IMAGE_SUCCESS` (0) on success.
+    pub(crate) type Image_ErrorCode = i32;
+
+    pub(crate) const IMAGE_SUCCESS: Image_ErrorCode = 0;
+
+    #[repr(C)]
+    pub struct OH_Pixelmap_InitializationOptions {
+        _private: [u8; 0],
+    }
+
+    #[repr(C)]
+    pub struct OH_Pixelmap_ImageInfo {
+        _private: [u8; 0],
+    }
+
+    #[cfg_attr(not(feature = "mock"), link(name = "pixelmap"))]
+    unsafe extern "C" {
+        pub(crate) fn OH_PixelmapInitializationOptions_Create(
+            options: *mut *mut OH_Pixelmap_InitializationOptions,
+        ) -> Image_ErrorCode;
+        pub(crate) fn OH_PixelmapInitializationOptions_SetWidth(
+            options: *mut OH_Pixelmap_InitializationOptions,
+            width: u32,
+        ) -> Image_ErrorCode;
+        pub(crate) fn OH_PixelmapInitializationOptions_SetHeight(
+            options: *mut OH_Pixelmap_InitializationOptions,
+            height: u32,
+        ) -> Image_ErrorCode;
+        pub(crate) fn OH_PixelmapInitializationOptions_SetPixelFormat(
+            options: *mut OH_Pixelmap_InitializationOptions,
+            pixel_format: i32,
+        ) -> Image_ErrorCode;
+        pub(crate) fn OH_PixelmapInitializationOptions_SetSrcPixelFormat(
+            options: *mut OH_Pixelmap_InitializationOptions,
+            src_pixel_format: i32,
+        ) -> Image_ErrorCode;
+        pub(crate) fn OH_PixelmapInitializationOptions_Release(
+            options: *mut OH_Pixelmap_InitializationOptions,
+        ) -> Image_ErrorCode;
+
+        pub(crate) fn OH_PixelmapImageInfo_Create(
+            info: *mut *mut OH_Pixelmap_ImageInfo,
+        ) -> Image_ErrorCode;
+        pub(crate) fn OH_PixelmapImageInfo_GetWidth(
+            info: *mut OH_Pixelmap_ImageInfo,
+            width: *mut u32,
+        ) -> Image_ErrorCode;
+        pub(crate) fn OH_PixelmapImageInfo_GetHeight(
+            info: *mut OH_Pixelmap_ImageInfo,
+            height: *mut u32,
+        ) -> Image_ErrorCode;
+        pub(crate) fn OH_PixelmapImageInfo_GetRowStride(
+            info: *mut OH_Pixelmap_ImageInfo,
+            row_stride: *mut u32,
+        ) -> Image_ErrorCode;
+        pub(crate) fn OH_PixelmapImageInfo_GetPixelFormat(
+            info: *mut OH_Pixelmap_ImageInfo,
+            pixel_format: *mut i32,
+        ) -> Image_ErrorCode;
+        pub(crate) fn OH_PixelmapImageInfo_Release(
+            info: *mut OH_Pixelmap_ImageInfo,
+        ) -> Image_ErrorCode;
+
+        pub(crate) fn OH_PixelmapNative_CreatePixelmap(
+            data: *mut u8,
+            data_length: usize,
+            options: *mut OH_Pixelmap_InitializationOptions,
+            pixel_map: *mut *mut OH_PixelmapNative,
+        ) -> Image_ErrorCode;
+        pub(crate) fn OH_PixelmapNative_ReadPixels(
+            pixel_map: *mut OH_PixelmapNative,
+            destination: *mut u8,
+            buffer_size: *mut usize,
+        ) -> Image_ErrorCode;
+        pub(crate) fn OH_PixelmapNative_GetImageInfo(
+            pixel_map: *mut OH_PixelmapNative,
+            image_info: *mut OH_Pixelmap_ImageInfo,
+        ) -> Image_ErrorCode;
+        pub(crate) fn OH_PixelmapNative_Release(
+            pixel_map: *mut OH_PixelmapNative,
+        ) -> Image_ErrorCode;
+    }
+}
+
+use ffi::*;
+
+/// Convert the `Image_ErrorCode` returned by `operation` into a `Result`.
+fn to_image_result(code: Image_ErrorCode, operation: &'static str) -> Result<()> {
+    if code == IMAGE_SUCCESS {
+        Ok(())
+    } else {
+        Err(UdmfError::Image { code, operation })
+    }
+}
+
+/// The layout of the pixels in a pixel map (`PIXEL_FORMAT`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum PixelFormat {
+    Rgb565,
+    Rgba8888,
+    Bgra8888,
+    Rgb888,
+    Alpha8,
+    RgbaF16,
+    Nv21,
+    Nv12,
+    /// `PIXEL_FORMAT_UNKNOWN` or a format added in a later API level.
+    Unknown(i32),
+}
+
+impl PixelFormat {
+    pub fn from_raw(raw: i32) -> Self {
+        match raw {
+            2 => Self::Rgb565,
+            3 => Self::Rgba8888,
+            4 => Self::Bgra8888,
+            5 => Self::Rgb888,
+            6 => Self::Alpha8,
+            7 => Self::RgbaF16,
+            8 => Self::Nv21,
+            9 => Self::Nv12,
+            other => Self::Unknown(other),
+        }
+    }
+
+    pub fn to_raw(self) -> i32 {
+        match self {
+            Self::Rgb565 => 2,
+            Self::Rgba8888 => 3,
+            Self::Bgra8888 => 4,
+            Self::Rgb888 => 5,
+            Self::Alpha8 => 6,
+            Self::RgbaF16 => 7,
+            Self::Nv21 => 8,
+            Self::Nv12 => 9,
+            Self::Unknown(raw) => raw,
+        }
+    }
+}
+
+/// The dimensions and layout of a pixel map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ImageInfo {
+    pub width: u32,
+    pub height: u32,
+    /// The number of bytes between the starts of two consecutive rows.
+    pub row_stride: u32,
+    pub pixel_format: PixelFormat,
+}
+
+/// Owns an `OH_Pixelmap_ImageInfo` while it is being read.
+struct RawImageInfo(*mut OH_Pixelmap_ImageInfo);
+
+impl RawImageInfo {
+    fn new() -> Result<Self> {
+        let mut info = std::ptr::null_mut();
+        // SAFETY: info is a valid out parameter.
+        let res = unsafe { OH_PixelmapImageInfo_Create(&mut info) };
+        to_image_result(res, "OH_PixelmapImageInfo_Create")?;
+        if info.is_null() {
+            return Err(UdmfError::null_pointer("OH_PixelmapImageInfo_Create"));
+        }
+        Ok(Self(info))
+    }
+}
+
+impl Drop for RawImageInfo {
+    fn drop(&mut self) {
+        // SAFETY: self.0 was created by OH_PixelmapImageInfo_Create and is released only once.
+        unsafe { OH_PixelmapImageInfo_Release(self.0) };
+    }
+}
+
+/// Owns an `OH_Pixelmap_InitializationOptions` while a pixel map is being created.
+struct RawOptions(*mut OH_Pixelmap_InitializationOptions);
+
+impl RawOptions {
+    fn new(width: u32, height: u32, format: PixelFormat) -> Result<Self> {
+        let mut options = std::ptr::null_mut();
+        // SAFETY: options is a valid out parameter.
+        let res = unsafe { OH_PixelmapInitializationOptions_Create(&mut options) };
+        to_image_result(res, "OH_PixelmapInitializationOptions_Create")?;
+        if options.is_null() {
+            return Err(UdmfError::null_pointer(
+                "OH_PixelmapInitializationOptions_Create",
+            ));
+        }
+        let options = Self(options);
+        // SAFETY: options.0 is a valid pointer to the options created above.
+        unsafe {
+            to_image_result(
+                OH_PixelmapInitializationOptions_SetWidth(options.0, width),
+                "OH_PixelmapInitializationOptions_SetWidth",
+            )?;
+            to_image_result(
+                OH_PixelmapInitializationOptions_SetHeight(options.0, height),
+                "OH_PixelmapInitializationOptions_SetHeight",
+            )?;
+            to_image_result(
+                OH_PixelmapInitializationOptions_SetSrcPixelFormat(options.0, format.to_raw()),
+                "OH_PixelmapInitializationOptions_SetSrcPixelFormat",
+            )?;
+            to_image_result(
+                OH_PixelmapInitializationOptions_SetPixelFormat(options.0, format.to_raw()),
+                "OH_PixelmapInitializationOptions_SetPixelFormat",
+            )?;
+        }
+        Ok(options)
+    }
+}
+
+impl Drop for RawOptions {
+    fn drop(&mut self) {
+        // SAFETY: self.0 was created by OH_PixelmapInitializationOptions_Create and is released
+        // only once.
+        unsafe { OH_PixelmapInitializationOptions_Release(self.0) };
+    }
+}
+
+/// A handle to an `OH_PixelmapNative`.
+///
+/// An owned handle is released when it is dropped. A borrowed handle is only valid for `'a` and
+/// is left to its owner.
+pub struct NativePixelMap<'a> {
+    inner: NonNull<OH_PixelmapNative>,
+    owned: bool,
+    _marker: PhantomData<&'a OH_PixelmapNative>,
+}
+
+// SAFETY: pixel maps of the image kit are reference counted and may be used from any thread.
+unsafe impl Send for NativePixelMap<'_> {}
+
+impl NativePixelMap<'static> {
+    /// Create a pixel map of `width` x `height` pixels in `format` from a copy of `data`.
+    ///
+    /// `data` holds the rows of the image without padding.
+    pub fn new(width: u32, height: u32, format: PixelFormat, data: &[u8]) -> Result<Self> {
+        let options = RawOptions::new(width, height, format)?;
+        // The image kit copies the data, it is only declared mutable.
+        let mut data = data.to_vec();
+        let mut pixel_map = std::ptr::null_mut();
+        // SAFETY: data is valid for data.len() bytes, options.0 is valid and pixel_map is a
+        // valid out parameter.
+        let res = unsafe {
+            OH_PixelmapNative_CreatePixelmap(
+                data.as_mut_ptr(),
+                data.len(),
+                options.0,
+                &mut pixel_map,
+            )
+        };
+        to_image_result(res, "OH_PixelmapNative_CreatePixelmap")?;
+        NonNull::new(pixel_map)
+            .map(|inner| Self {
+                inner,
+                owned: true,
+                _marker: PhantomData,
+            })
+            .ok_or(UdmfError::null_pointer("OH_PixelmapNative_CreatePixelmap"))
+    }
+
+    /// Take ownership of `ptr`, which is released when the handle is dropped.
+    ///
+    /// Returns `None` if `ptr` is null.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be a valid pixel map that is not released by anyone else.
+    pub unsafe fn from_raw(ptr: *mut OH_PixelmapNative) -> Option<Self> {
+        NonNull::new(ptr).map(|inner| Self {
+            inner,
+            owned: true,
+            _marker: PhantomData,
+        })
+    }
+}
+
+impl<'a> NativePixelMap<'a> {
+    /// Borrow `ptr` without taking ownership.
+    ///
+    /// Returns `None` if `ptr` is null.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be a valid pixel map that outlives `'a`.
+    pub unsafe fn from_ptr(ptr: *mut OH_PixelmapNative) -> Option<Self> {
+        NonNull::new(ptr).map(|inner| Self {
+            inner,
+            owned: false,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Get the raw handle, which stays owned by `self`.
+    pub fn as_ptr(&self) -> *mut OH_PixelmapNative {
+        self.inner.as_ptr()
+    }
+
+    /// Whether the handle is released when `self` is dropped.
+    pub fn is_owned(&self) -> bool {
+        self.owned
+    }
+
+    /// Get the dimensions and layout of the pixel map.
+    pub fn get_image_info(&self) -> Result<ImageInfo> {
+        let info = RawImageInfo::new()?;
+        // SAFETY: self.inner and info.0 are valid pointers.
+        let res = unsafe { OH_PixelmapNative_GetImageInfo(self.as_ptr(), info.0) };
+        to_image_result(res, "OH_PixelmapNative_GetImageInfo")?;
+        let (mut width, mut height, mut row_stride, mut pixel_format) = (0, 0, 0, 0);
+        // SAFETY: info.0 is valid and the out parameters point to local variables.
+        unsafe {
+            to_image_result(
+                OH_PixelmapImageInfo_GetWidth(info.0, &mut width),
+                "OH_PixelmapImageInfo_GetWidth",
+            )?;
+            to_image_result(
+                OH_PixelmapImageInfo_GetHeight(info.0, &mut height),
+                "OH_PixelmapImageInfo_GetHeight",
+            )?;
+            to_image_result(
+                OH_PixelmapImageInfo_GetRowStride(info.0, &mut row_stride),
+                "OH_PixelmapImageInfo_GetRowStride",
+            )?;
+            to_image_result(
+                OH_PixelmapImageInfo_GetPixelFormat(info.0, &mut pixel_format),
+                "OH_PixelmapImageInfo_GetPixelFormat",
+            )?;
+        }
+        Ok(ImageInfo {
+            width,
+            height,
+            row_stride,
+            pixel_format: PixelFormat::from_raw(pixel_format),
+        })
+    }
+
+    /// Copy the pixels, as laid out by [`ImageInfo::row_stride`] and
+    /// [`ImageInfo::pixel_format`].
+    pub fn read_pixels(&self) -> Result<Vec<u8>> {
+        let info = self.get_image_info()?;
+        let mut len = info.row_stride as usize * info.height as usize;
+        let mut pixels = vec![0; len];
+        // SAFETY: self.inner is valid and pixels is valid for writes of len bytes.
+        let res =
+            unsafe { OH_PixelmapNative_ReadPixels(self.as_ptr(), pixels.as_mut_ptr(), &mut len) };
+        to_image_result(res, "OH_PixelmapNative_ReadPixels")?;
+        pixels.truncate(len);
+        Ok(pixels)
+    }
+
+    pub fn get_width(&self) -> Result<u32> {
+        self.get_image_info().map(|info| info.width)
+    }
+
+    pub fn get_height(&self) -> Result<u32> {
+        self.get_image_info().map(|info| info.height)
+    }
+
+    pub fn get_pixel_format(&self) -> Result<PixelFormat> {
+        self.get_image_info().map(|info| info.pixel_format)
+    }
+
+    pub fn get_row_stride(&self) -> Result<u32> {
+        self.get_image_info().map(|info| info.row_stride)
+    }
+}
+
+impl Drop for NativePixelMap<'_> {
+    fn drop(&mut self) {
+        if self.owned {
+            // SAFETY: the handle is owned by self and released only once.
+            unsafe { OH_PixelmapNative_Release(self.as_ptr()) };
+        }
+    }
+}
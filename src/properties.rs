@@ -0,0 +1,162 @@
+use crate::error::{Result, UdmfError, to_result};
+use crate::unified_data::UnifiedData;
+use ohos_sys_opaque_types::*;
+use std::ffi::{CStr, CString};
+use udmf_sys::data_management_framework::*;
+
+/// The range in which a [`UnifiedData`] may be used on this device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ShareOption {
+    /// The data may only be used within the same application.
+    InApp,
+    /// The data may be used across applications.
+    CrossApp,
+}
+
+impl From<ShareOption> for Udmf_ShareOption {
+    fn from(option: ShareOption) -> Self {
+        match option {
+            ShareOption::InApp => Udmf_ShareOption::IN_APP,
+            ShareOption::CrossApp => Udmf_ShareOption::CROSS_APP,
+        }
+    }
+}
+
+/// The properties of a [`UnifiedData`], such as tag, timestamp and share option.
+///
+/// The properties are shared with the `UnifiedData` they were created from, so changes are
+/// visible on the data.
+pub struct UnifiedDataProperties {
+    inner: *mut OH_UdmfProperty,
+}
+
+impl UnifiedDataProperties {
+    /// Get the properties of `data`.
+    pub fn new(data: &UnifiedData) -> Result<Self> {
+        // SAFETY: data.as_ptr() is a valid pointer to OH_UdmfData.
+        let inner = unsafe { OH_UdmfProperty_Create(data.as_ptr()) };
+        if inner.is_null() {
+            return Err(UdmfError::null_pointer("OH_UdmfProperty_Create"));
+        }
+        Ok(Self { inner })
+    }
+
+    pub fn get_tag(&self) -> String {
+        self.get_tag_cstr()
+            .map(|c| c.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    }
+
+    pub fn get_tag_cstr(&self) -> Option<&CStr> {
+        // SAFETY: self.inner is a valid pointer to OH_UdmfProperty.
+        let c_ptr = unsafe { OH_UdmfProperty_GetTag(self.inner) };
+        if c_ptr.is_null() {
+            None
+        } else {
+            // SAFETY: c_ptr is a valid C string returned by UDMF and it is valid as long as self is alive.
+            Some(unsafe { CStr::from_ptr(c_ptr) })
+        }
+    }
+
+    pub fn set_tag(&mut self, tag: &str) -> Result<()> {
+        let c_tag =
+            CString::new(tag).map_err(|_| UdmfError::invalid_param("OH_UdmfProperty_SetTag"))?;
+        self.set_tag_cstr(&c_tag)
+    }
+
+    pub fn set_tag_cstr(&mut self, tag: &CStr) -> Result<()> {
+        // SAFETY: self.inner is a valid pointer and tag is a valid C string.
+        let res = unsafe { OH_UdmfProperty_SetTag(self.inner, tag.as_ptr()) };
+        to_result(res, "OH_UdmfProperty_SetTag")
+    }
+
+    /// Get the creation time of the data in milliseconds since the UNIX epoch.
+    pub fn get_timestamp(&self) -> i64 {
+        // SAFETY: self.inner is a valid pointer to OH_UdmfProperty.
+        unsafe { OH_UdmfProperty_GetTimestamp(self.inner) }
+    }
+
+    /// Get the share option, or `None` if it is invalid.
+    pub fn get_share_option(&self) -> Option<ShareOption> {
+        // SAFETY: self.inner is a valid pointer to OH_UdmfProperty.
+        match unsafe { OH_UdmfProperty_GetShareOption(self.inner) } {
+            Udmf_ShareOption::IN_APP => Some(ShareOption::InApp),
+            Udmf_ShareOption::CROSS_APP => Some(ShareOption::CrossApp),
+            _ => None,
+        }
+    }
+
+    pub fn set_share_option(&mut self, option: ShareOption) -> Result<()> {
+        // SAFETY: self.inner is a valid pointer to OH_UdmfProperty.
+        let res = unsafe { OH_UdmfProperty_SetShareOption(self.inner, option.into()) };
+        to_result(res, "OH_UdmfProperty_SetShareOption")
+    }
+
+    /// Get the integer extras parameter `key`, or `default` if it is not set.
+    pub fn get_extras_int(&self, key: &str, default: i32) -> i32 {
+        let Ok(c_key) = CString::new(key) else {
+            return default;
+        };
+        self.get_extras_int_cstr(&c_key, default)
+    }
+
+    pub fn get_extras_int_cstr(&self, key: &CStr, default: i32) -> i32 {
+        // SAFETY: self.inner is a valid pointer and key is a valid C string.
+        unsafe { OH_UdmfProperty_GetExtrasIntParam(self.inner, key.as_ptr(), default) }
+    }
+
+    pub fn set_extras_int(&mut self, key: &str, value: i32) -> Result<()> {
+        let c_key = CString::new(key)
+            .map_err(|_| UdmfError::invalid_param("OH_UdmfProperty_SetExtrasIntParam"))?;
+        self.set_extras_int_cstr(&c_key, value)
+    }
+
+    pub fn set_extras_int_cstr(&mut self, key: &CStr, value: i32) -> Result<()> {
+        // SAFETY: self.inner is a valid pointer and key is a valid C string.
+        let res = unsafe { OH_UdmfProperty_SetExtrasIntParam(self.inner, key.as_ptr(), value) };
+        to_result(res, "OH_UdmfProperty_SetExtrasIntParam")
+    }
+
+    /// Get the string extras parameter `key`, or `None` if it is not set.
+    pub fn get_extras_string(&self, key: &str) -> Option<String> {
+        let c_key = CString::new(key).ok()?;
+        self.get_extras_string_cstr(&c_key)
+            .map(|c| c.to_string_lossy().into_owned())
+    }
+
+    pub fn get_extras_string_cstr(&self, key: &CStr) -> Option<&CStr> {
+        // SAFETY: self.inner is a valid pointer and key is a valid C string.
+        let c_ptr = unsafe { OH_UdmfProperty_GetExtrasStringParam(self.inner, key.as_ptr()) };
+        if c_ptr.is_null() {
+            None
+        } else {
+            // SAFETY: c_ptr is a valid C string returned by UDMF and it is valid as long as self is alive.
+            Some(unsafe { CStr::from_ptr(c_ptr) })
+        }
+    }
+
+    pub fn set_extras_string(&mut self, key: &str, value: &str) -> Result<()> {
+        let c_key = CString::new(key)
+            .map_err(|_| UdmfError::invalid_param("OH_UdmfProperty_SetExtrasStringParam"))?;
+        let c_value = CString::new(value)
+            .map_err(|_| UdmfError::invalid_param("OH_UdmfProperty_SetExtrasStringParam"))?;
+        self.set_extras_string_cstr(&c_key, &c_value)
+    }
+
+    pub fn set_extras_string_cstr(&mut self, key: &CStr, value: &CStr) -> Result<()> {
+        // SAFETY: self.inner is a valid pointer and key and value are valid C strings.
+        let res = unsafe {
+            OH_UdmfProperty_SetExtrasStringParam(self.inner, key.as_ptr(), value.as_ptr())
+        };
+        to_result(res, "OH_UdmfProperty_SetExtrasStringParam")
+    }
+}
+
+impl Drop for UnifiedDataProperties {
+    fn drop(&mut self) {
+        if !self.inner.is_null() {
+            // SAFETY: we own the inner pointer and it is valid.
+            unsafe { OH_UdmfProperty_Destroy(self.inner) };
+        }
+    }
+}
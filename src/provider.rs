@@ -0,0 +1,105 @@
+use crate::entry::Entry;
+use crate::error::{Result, UdmfError, to_result};
+use crate::types::UniformDataType;
+use crate::unified_data::UnifiedRecord;
+use std::ffi::{CStr, c_char, c_void};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use udmf_sys::data_management_framework::*;
+
+/// Calls the provider closure stored in `context` for the requested type.
+///
+/// Panics must not unwind into UDMF, so they are caught and reported as missing data.
+unsafe extern "C" fn get_data_trampoline<F>(
+    context: *mut c_void,
+    type_id: *const c_char,
+) -> *mut c_void
+where
+    F: Fn(&UniformDataType) -> Option<Entry> + Send + Sync + 'static,
+{
+    if context.is_null() || type_id.is_null() {
+        return std::ptr::null_mut();
+    }
+    // SAFETY: context was created from a `Box<F>` in `UnifiedRecord::set_provider` and is only
+    // released by `finalize_trampoline`.
+    let provider = unsafe { &*(context as *const F) };
+    // Converting the type and the entry runs user code such as `Drop` impls as well, so
+    // everything after the null checks stays inside `catch_unwind`.
+    catch_unwind(AssertUnwindSafe(|| {
+        // SAFETY: type_id is a valid C string provided by UDMF.
+        let type_id = UniformDataType::from(unsafe { CStr::from_ptr(type_id) });
+        match provider(&type_id) {
+            // Ownership of the UDS object is passed to UDMF.
+            Some(entry) => entry.into_raw_uds().unwrap_or(std::ptr::null_mut()),
+            None => std::ptr::null_mut(),
+        }
+    }))
+    .unwrap_or(std::ptr::null_mut())
+}
+
+/// Releases the provider closure once UDMF destroys the provider.
+unsafe extern "C" fn finalize_trampoline<F>(context: *mut c_void) {
+    if context.is_null() {
+        return;
+    }
+    // SAFETY: context was created from a `Box<F>` in `UnifiedRecord::set_provider` and UDMF calls
+    // the finalizer exactly once.
+    let provider = unsafe { Box::from_raw(context as *mut F) };
+    // A panicking destructor must not unwind into UDMF.
+    let _ = catch_unwind(AssertUnwindSafe(move || drop(provider)));
+}
+
+impl UnifiedRecord {
+    /// Advertise `types` on this record and produce their data lazily.
+    ///
+    /// `provider` is called with the requested type when a consumer reads one of `types` and
+    /// returns `None` if no data is available. It may be called from any thread, which is why it
+    /// must be `Send` and `Sync`. The record takes ownership of the provider and drops it when
+    /// the record is destroyed.
+    pub fn set_provider<F>(&mut self, types: &[UniformDataType], provider: F) -> Result<()>
+    where
+        F: Fn(&UniformDataType) -> Option<Entry> + Send + Sync + 'static,
+    {
+        // SAFETY: OH_UdmfRecordProvider_Create creates a new instance of OH_UdmfRecordProvider.
+        let raw_provider = unsafe { OH_UdmfRecordProvider_Create() };
+        if raw_provider.is_null() {
+            return Err(UdmfError::null_pointer("OH_UdmfRecordProvider_Create"));
+        }
+
+        let context = Box::into_raw(Box::new(provider)) as *mut c_void;
+        // SAFETY: raw_provider is valid and context points to a boxed `F` matching the trampolines.
+        let res = unsafe {
+            OH_UdmfRecordProvider_SetData(
+                raw_provider,
+                context,
+                Some(get_data_trampoline::<F>),
+                Some(finalize_trampoline::<F>),
+            )
+        };
+        if let Err(e) = to_result(res, "OH_UdmfRecordProvider_SetData") {
+            // SAFETY: the context was not handed over to UDMF, so we still own it.
+            drop(unsafe { Box::from_raw(context as *mut F) });
+            // SAFETY: raw_provider is valid and owned by us.
+            unsafe { OH_UdmfRecordProvider_Destroy(raw_provider) };
+            return Err(e);
+        }
+
+        let type_ptrs: Vec<*const c_char> = types.iter().map(|t| t.to_cstr().as_ptr()).collect();
+        // SAFETY: self.inner and raw_provider are valid, and type_ptrs holds `types.len()` valid
+        // C strings which outlive the call.
+        let res = unsafe {
+            OH_UdmfRecord_SetProvider(
+                self.inner,
+                type_ptrs.as_ptr(),
+                type_ptrs.len() as u32,
+                raw_provider,
+            )
+        };
+        if let Err(e) = to_result(res, "OH_UdmfRecord_SetProvider") {
+            // SAFETY: the record did not take the provider. Destroying it calls the finalizer,
+            // which releases the context.
+            unsafe { OH_UdmfRecordProvider_Destroy(raw_provider) };
+            return Err(e);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,178 @@
+//! Owned copies of uniform data structures.
+//!
+//! The wrappers in [`crate::uds`] own a UDMF handle and can only be accessed through getters.
+//! The snapshots in this module are plain Rust structs holding a copy of all fields, so they
+//! can be stored, compared, sent to other threads and, with the `serde` feature, serialized.
+//!
+//! ```no_run
+//! # fn main() -> udmf::Result<()> {
+//! use udmf::PlainText;
+//!
+//! let mut text = PlainText::new()?;
+//! text.set_content("Hello")?;
+//! let snapshot = text.snapshot();
+//! assert_eq!(snapshot.content, "Hello");
+//!
+//! let restored = PlainText::try_from(&snapshot)?;
+//! assert_eq!(restored.get_content(), "Hello");
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::Result;
+use crate::uds::{AppItem, ArrayBuffer, ContentForm, FileUri, Html, Hyperlink, PlainText};
+
+/// Define a snapshot struct of a UDS made up of string fields.
+macro_rules! string_snapshot {
+    (
+        $(#[$meta:meta])*
+        $name:ident for $uds:ident {
+            $($(#[$field_meta:meta])* $field:ident: $getter:ident / $setter:ident,)+
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        pub struct $name {
+            $($(#[$field_meta])* pub $field: String,)+
+        }
+
+        impl $uds {
+            /// Copy all fields into an owned snapshot.
+            pub fn snapshot(&self) -> $name {
+                $name {
+                    $($field: self.$getter(),)+
+                }
+            }
+        }
+
+        impl TryFrom<&$name> for $uds {
+            type Error = crate::UdmfError;
+
+            fn try_from(snapshot: &$name) -> Result<Self> {
+                let mut uds = Self::new()?;
+                $(uds.$setter(&snapshot.$field)?;)+
+                Ok(uds)
+            }
+        }
+    };
+}
+
+string_snapshot! {
+    /// Owned copy of a [`PlainText`].
+    PlainTextSnapshot for PlainText {
+        content: get_content / set_content,
+        abstract_text: get_abstract / set_abstract,
+    }
+}
+
+string_snapshot! {
+    /// Owned copy of a [`Hyperlink`].
+    HyperlinkSnapshot for Hyperlink {
+        url: get_url / set_url,
+        description: get_description / set_description,
+    }
+}
+
+string_snapshot! {
+    /// Owned copy of an [`Html`].
+    HtmlSnapshot for Html {
+        content: get_content / set_content,
+        plain_content: get_plain_content / set_plain_content,
+    }
+}
+
+string_snapshot! {
+    /// Owned copy of an [`AppItem`].
+    AppItemSnapshot for AppItem {
+        id: get_id / set_id,
+        name: get_name / set_name,
+        icon_id: get_icon_id / set_icon_id,
+        label_id: get_label_id / set_label_id,
+        bundle_name: get_bundle_name / set_bundle_name,
+        ability_name: get_ability_name / set_ability_name,
+    }
+}
+
+string_snapshot! {
+    /// Owned copy of a [`FileUri`].
+    FileUriSnapshot for FileUri {
+        file_uri: get_file_uri / set_file_uri,
+        file_type: get_file_type / set_file_type,
+    }
+}
+
+/// Owned copy of a [`ContentForm`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ContentFormSnapshot {
+    pub title: String,
+    pub description: String,
+    pub app_name: String,
+    pub link_uri: String,
+    pub thumb_data: Vec<u8>,
+    pub app_icon: Vec<u8>,
+}
+
+impl ContentForm {
+    /// Copy all fields into an owned snapshot.
+    pub fn snapshot(&self) -> Result<ContentFormSnapshot> {
+        Ok(ContentFormSnapshot {
+            title: self.get_title(),
+            description: self.get_description(),
+            app_name: self.get_app_name(),
+            link_uri: self.get_link_uri(),
+            thumb_data: self.get_thumb_data()?,
+            app_icon: self.get_app_icon()?,
+        })
+    }
+}
+
+impl TryFrom<&ContentFormSnapshot> for ContentForm {
+    type Error = crate::UdmfError;
+
+    fn try_from(snapshot: &ContentFormSnapshot) -> Result<Self> {
+        let mut form = Self::new()?;
+        form.set_title(&snapshot.title)?;
+        form.set_description(&snapshot.description)?;
+        form.set_app_name(&snapshot.app_name)?;
+        form.set_link_uri(&snapshot.link_uri)?;
+        // UDMF rejects empty buffers, so unset images are left unset.
+        if !snapshot.thumb_data.is_empty() {
+            form.set_thumb_data(&snapshot.thumb_data)?;
+        }
+        if !snapshot.app_icon.is_empty() {
+            form.set_app_icon(&snapshot.app_icon)?;
+        }
+        Ok(form)
+    }
+}
+
+/// Owned copy of an [`ArrayBuffer`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ArrayBufferSnapshot {
+    pub data: Vec<u8>,
+}
+
+impl ArrayBuffer {
+    /// Copy the data into an owned snapshot.
+    pub fn snapshot(&self) -> Result<ArrayBufferSnapshot> {
+        Ok(ArrayBufferSnapshot {
+            data: self.get_data()?,
+        })
+    }
+}
+
+impl TryFrom<&ArrayBufferSnapshot> for ArrayBuffer {
+    type Error = crate::UdmfError;
+
+    fn try_from(snapshot: &ArrayBufferSnapshot) -> Result<Self> {
+        let mut buffer = Self::new()?;
+        // UDMF rejects empty buffers, so an empty snapshot leaves the buffer unset.
+        if !snapshot.data.is_empty() {
+            buffer.set_data(&snapshot.data)?;
+        }
+        Ok(buffer)
+    }
+}
@@ -0,0 +1,318 @@
+use crate::error::{Result, UdmfError, to_result};
+use crate::unified_data::UnifiedData;
+#[cfg(feature = "api-20")]
+use ohos_sys_opaque_types::OH_UdmfData;
+use std::ffi::{CStr, CString};
+use std::fmt;
+use std::os::raw::c_char;
+use udmf_sys::data_management_framework::*;
+
+/// The intention of data stored in the UDMF database.
+///
+/// The intention selects the data channel, e.g. drag and drop or the pasteboard.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Intention {
+    Drag,
+    Pasteboard,
+    #[cfg(feature = "api-20")]
+    DataHub,
+    #[cfg(feature = "api-20")]
+    SystemShare,
+    #[cfg(feature = "api-20")]
+    Picker,
+    #[cfg(feature = "api-20")]
+    Menu,
+}
+
+impl From<Intention> for Udmf_Intention {
+    fn from(intention: Intention) -> Self {
+        match intention {
+            Intention::Drag => Udmf_Intention::DRAG,
+            Intention::Pasteboard => Udmf_Intention::PASTEBOARD,
+            #[cfg(feature = "api-20")]
+            Intention::DataHub => Udmf_Intention::DATA_HUB,
+            #[cfg(feature = "api-20")]
+            Intention::SystemShare => Udmf_Intention::SYSTEM_SHARE,
+            #[cfg(feature = "api-20")]
+            Intention::Picker => Udmf_Intention::PICKER,
+            #[cfg(feature = "api-20")]
+            Intention::Menu => Udmf_Intention::MENU,
+        }
+    }
+}
+
+impl TryFrom<Udmf_Intention> for Intention {
+    type Error = UdmfError;
+
+    fn try_from(intention: Udmf_Intention) -> Result<Self> {
+        match intention {
+            Udmf_Intention::DRAG => Ok(Self::Drag),
+            Udmf_Intention::PASTEBOARD => Ok(Self::Pasteboard),
+            #[cfg(feature = "api-20")]
+            Udmf_Intention::DATA_HUB => Ok(Self::DataHub),
+            #[cfg(feature = "api-20")]
+            Udmf_Intention::SYSTEM_SHARE => Ok(Self::SystemShare),
+            #[cfg(feature = "api-20")]
+            Udmf_Intention::PICKER => Ok(Self::Picker),
+            #[cfg(feature = "api-20")]
+            Udmf_Intention::MENU => Ok(Self::Menu),
+            _ => Err(UdmfError::invalid_param("Udmf_Intention")),
+        }
+    }
+}
+
+/// The key under which a [`UnifiedData`] is stored in the UDMF database.
+///
+/// Keys are returned by [`set_unified_data`] and have the form
+/// `udmf://<intention>/<bundle name>/<id>`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DataKey(CString);
+
+impl DataKey {
+    pub fn new(key: &str) -> Result<Self> {
+        CString::new(key)
+            .map(Self)
+            .map_err(|_| UdmfError::invalid_param("DataKey::new"))
+    }
+
+    pub fn from_cstr(key: &CStr) -> Self {
+        Self(key.to_owned())
+    }
+
+    pub fn as_cstr(&self) -> &CStr {
+        &self.0
+    }
+}
+
+impl fmt::Display for DataKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0.to_string_lossy())
+    }
+}
+
+/// Store `data` in the UDMF database and return the key it was stored under.
+pub fn set_unified_data(intention: Intention, data: &UnifiedData) -> Result<DataKey> {
+    let mut key = [0 as c_char; UDMF_KEY_BUFFER_LEN as usize];
+    // SAFETY: data.as_ptr() is a valid pointer and key is a writable buffer of UDMF_KEY_BUFFER_LEN bytes.
+    let res = unsafe {
+        OH_Udmf_SetUnifiedData(
+            intention.into(),
+            data.as_ptr(),
+            key.as_mut_ptr(),
+            UDMF_KEY_BUFFER_LEN,
+        )
+    };
+    to_result(res, "OH_Udmf_SetUnifiedData")?;
+    // SAFETY: UDMF wrote a NUL-terminated key into the buffer.
+    Ok(DataKey::from_cstr(unsafe { CStr::from_ptr(key.as_ptr()) }))
+}
+
+/// Retrieve the data stored under `key` from the UDMF database.
+pub fn get_unified_data(key: &DataKey, intention: Intention) -> Result<UnifiedData> {
+    let data = UnifiedData::new()?;
+    // SAFETY: key is a valid C string and data.as_ptr() is a valid pointer that UDMF fills in.
+    let res =
+        unsafe { OH_Udmf_GetUnifiedData(key.as_cstr().as_ptr(), intention.into(), data.as_ptr()) };
+    to_result(res, "OH_Udmf_GetUnifiedData")?;
+    Ok(data)
+}
+
+/// Visibility of data stored in the UDMF database.
+#[cfg(feature = "api-20")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Visibility {
+    /// Any application can obtain the data.
+    #[default]
+    All,
+    /// Only the data provider can obtain the data.
+    OwnProcess,
+}
+
+#[cfg(feature = "api-20")]
+impl From<Visibility> for Udmf_Visibility {
+    fn from(visibility: Visibility) -> Self {
+        match visibility {
+            Visibility::All => Udmf_Visibility::UDMF_ALL,
+            Visibility::OwnProcess => Udmf_Visibility::UDMF_OWN_PROCESS,
+        }
+    }
+}
+
+/// Options for the option-based database operations.
+#[cfg(feature = "api-20")]
+pub struct Options {
+    inner: *mut OH_UdmfOptions,
+}
+
+#[cfg(feature = "api-20")]
+impl Options {
+    pub fn new() -> Result<Self> {
+        // SAFETY: OH_UdmfOptions_Create creates a new instance of OH_UdmfOptions.
+        let inner = unsafe { OH_UdmfOptions_Create() };
+        if inner.is_null() {
+            return Err(UdmfError::null_pointer("OH_UdmfOptions_Create"));
+        }
+        Ok(Self { inner })
+    }
+
+    pub fn get_key(&self) -> Option<DataKey> {
+        // SAFETY: self.inner is a valid pointer to OH_UdmfOptions.
+        let c_ptr = unsafe { OH_UdmfOptions_GetKey(self.inner) };
+        if c_ptr.is_null() {
+            None
+        } else {
+            // SAFETY: c_ptr is a valid C string returned by UDMF.
+            Some(DataKey::from_cstr(unsafe { CStr::from_ptr(c_ptr) }))
+        }
+    }
+
+    pub fn set_key(&mut self, key: &DataKey) -> Result<()> {
+        // SAFETY: self.inner is a valid pointer and key is a valid C string.
+        let res = unsafe { OH_UdmfOptions_SetKey(self.inner, key.as_cstr().as_ptr()) };
+        to_result(res, "OH_UdmfOptions_SetKey")
+    }
+
+    pub fn get_intention(&self) -> Option<Intention> {
+        // SAFETY: self.inner is a valid pointer to OH_UdmfOptions.
+        let intention = unsafe { OH_UdmfOptions_GetIntention(self.inner) };
+        Intention::try_from(intention).ok()
+    }
+
+    pub fn set_intention(&mut self, intention: Intention) -> Result<()> {
+        // SAFETY: self.inner is a valid pointer to OH_UdmfOptions.
+        let res = unsafe { OH_UdmfOptions_SetIntention(self.inner, intention.into()) };
+        to_result(res, "OH_UdmfOptions_SetIntention")
+    }
+
+    pub fn get_visibility(&self) -> Visibility {
+        // SAFETY: self.inner is a valid pointer to OH_UdmfOptions.
+        let visibility = unsafe { OH_UdmfOptions_GetVisibility(self.inner) };
+        if visibility == Udmf_Visibility::UDMF_OWN_PROCESS {
+            Visibility::OwnProcess
+        } else {
+            Visibility::All
+        }
+    }
+
+    pub fn set_visibility(&mut self, visibility: Visibility) -> Result<()> {
+        // SAFETY: self.inner is a valid pointer to OH_UdmfOptions.
+        let res = unsafe { OH_UdmfOptions_SetVisibility(self.inner, visibility.into()) };
+        to_result(res, "OH_UdmfOptions_SetVisibility")
+    }
+
+    /// Reset all options to their defaults.
+    pub fn reset(&mut self) -> Result<()> {
+        // SAFETY: self.inner is a valid pointer to OH_UdmfOptions.
+        let res = unsafe { OH_UdmfOptions_Reset(self.inner) };
+        to_result(res, "OH_UdmfOptions_Reset")
+    }
+}
+
+#[cfg(feature = "api-20")]
+impl Drop for Options {
+    fn drop(&mut self) {
+        if !self.inner.is_null() {
+            // SAFETY: we own the inner pointer and it is valid.
+            unsafe { OH_UdmfOptions_Destroy(self.inner) };
+        }
+    }
+}
+
+/// Store `data` in the UDMF database using the intention and visibility of `options`.
+#[cfg(feature = "api-20")]
+pub fn set_unified_data_with_options(options: &Options, data: &UnifiedData) -> Result<DataKey> {
+    let mut key = [0 as c_char; UDMF_KEY_BUFFER_LEN as usize];
+    // SAFETY: options.inner and data.as_ptr() are valid pointers and key is a writable buffer
+    // of UDMF_KEY_BUFFER_LEN bytes.
+    let res = unsafe {
+        OH_Udmf_SetUnifiedDataByOptions(
+            options.inner,
+            data.as_ptr(),
+            key.as_mut_ptr(),
+            UDMF_KEY_BUFFER_LEN,
+        )
+    };
+    to_result(res, "OH_Udmf_SetUnifiedDataByOptions")?;
+    // SAFETY: UDMF wrote a NUL-terminated key into the buffer.
+    Ok(DataKey::from_cstr(unsafe { CStr::from_ptr(key.as_ptr()) }))
+}
+
+/// Replace the data stored under the key of `options` with `data`.
+#[cfg(feature = "api-20")]
+pub fn update_unified_data(options: &Options, data: &UnifiedData) -> Result<()> {
+    // SAFETY: options.inner and data.as_ptr() are valid pointers.
+    let res = unsafe { OH_Udmf_UpdateUnifiedData(options.inner, data.as_ptr()) };
+    to_result(res, "OH_Udmf_UpdateUnifiedData")
+}
+
+/// Get all data selected by the key, intention and visibility of `options`.
+///
+/// The returned data is a copy of the stored data. It has the stored records, tag and share
+/// option, but not the timestamp or extras of the stored properties.
+#[cfg(feature = "api-20")]
+pub fn get_unified_data_with_options(options: &Options) -> Result<Vec<UnifiedData>> {
+    let mut data_array: *mut OH_UdmfData = std::ptr::null_mut();
+    let mut count: u32 = 0;
+    // SAFETY: options.inner is a valid pointer. data_array and count will be initialized by the FFI call.
+    let res =
+        unsafe { OH_Udmf_GetUnifiedDataByOptions(options.inner, &mut data_array, &mut count) };
+    to_result(res, "OH_Udmf_GetUnifiedDataByOptions")?;
+    // SAFETY: data_array and count were returned by OH_Udmf_GetUnifiedDataByOptions.
+    unsafe { take_data_array(data_array, count) }
+}
+
+/// Delete the data selected by `options` and return the deleted data.
+///
+/// Like with [`get_unified_data_with_options`], the returned data keeps the records, tag and
+/// share option, but not the timestamp or extras of the stored properties.
+#[cfg(feature = "api-20")]
+pub fn delete_unified_data(options: &Options) -> Result<Vec<UnifiedData>> {
+    let mut data_array: *mut OH_UdmfData = std::ptr::null_mut();
+    let mut count: u32 = 0;
+    // SAFETY: options.inner is a valid pointer. data_array and count will be initialized by the FFI call.
+    let res = unsafe { OH_Udmf_DeleteUnifiedData(options.inner, &mut data_array, &mut count) };
+    to_result(res, "OH_Udmf_DeleteUnifiedData")?;
+    // SAFETY: data_array and count were returned by OH_Udmf_DeleteUnifiedData.
+    unsafe { take_data_array(data_array, count) }
+}
+
+// `OH_UdmfData` is opaque, so the elements of a data array can only be addressed through this
+// accessor. It is declared in `udmf.h` and exported by `libudmf.so` of the API level 20 SDK,
+// but udmf-sys 0.1.3 has no binding for it yet. Keep the signature in sync with the header
+// until it does.
+#[cfg(feature = "api-20")]
+#[link(name = "udmf")]
+unsafe extern "C" {
+    fn OH_Udmf_GetDataElementAt(data_array: *mut *mut OH_UdmfData, index: u32) -> *mut OH_UdmfData;
+}
+
+/// Copy the elements of a data array returned by UDMF into owned data and destroy the array.
+///
+/// # Safety
+///
+/// `data_array` must be null or an array of `count` elements returned by UDMF that is not used
+/// afterwards.
+#[cfg(feature = "api-20")]
+unsafe fn take_data_array(
+    mut data_array: *mut OH_UdmfData,
+    count: u32,
+) -> Result<Vec<UnifiedData>> {
+    if data_array.is_null() {
+        return Ok(Vec::new());
+    }
+    let copied = (0..count)
+        .map(|index| {
+            // SAFETY: data_array is a valid array of count elements.
+            let element = unsafe { OH_Udmf_GetDataElementAt(&mut data_array, index) };
+            if element.is_null() {
+                return Err(UdmfError::null_pointer("OH_Udmf_GetDataElementAt"));
+            }
+            // SAFETY: element belongs to data_array, which outlives the borrow.
+            unsafe { UnifiedData::from_ptr(element, false) }.copy()
+        })
+        .collect();
+    // SAFETY: data_array was returned by UDMF and must be destroyed, also if copying failed.
+    unsafe { OH_Udmf_DestroyDataArray(&mut data_array, count) };
+    copied
+}
@@ -0,0 +1,63 @@
+//! A process-wide cache of resolved type descriptors.
+
+use crate::error::Result;
+use crate::types::UniformDataType;
+use crate::utd::{TypeDescriptor, TypeInfo};
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, PoisonError, RwLock};
+
+/// A thread-safe cache of [`TypeInfo`] keyed by type.
+///
+/// Each type is resolved through [`TypeDescriptor`] once, later lookups only clone an `Arc`.
+/// Failed lookups are not cached.
+///
+/// ```no_run
+/// use udmf::{TypeCache, UniformDataType};
+///
+/// let info = TypeCache::global().get(&UniformDataType::PlainText)?;
+/// assert!(info.mime_types.iter().any(|m| m == "text/plain"));
+/// # Ok::<(), udmf::UdmfError>(())
+/// ```
+#[derive(Debug, Default)]
+pub struct TypeCache {
+    types: RwLock<HashMap<UniformDataType, Arc<TypeInfo>>>,
+}
+
+static GLOBAL: LazyLock<TypeCache> = LazyLock::new(TypeCache::new);
+
+impl TypeCache {
+    /// Create an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the cache shared by the whole process.
+    pub fn global() -> &'static TypeCache {
+        &GLOBAL
+    }
+
+    /// Get the information about `type_id`, resolving it on first use.
+    pub fn get(&self, type_id: &UniformDataType) -> Result<Arc<TypeInfo>> {
+        if let Some(info) = self
+            .types
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(type_id)
+        {
+            return Ok(Arc::clone(info));
+        }
+        // Resolve without holding the lock. If another thread resolved the type in the
+        // meantime, its entry is kept.
+        let info = Arc::new(TypeDescriptor::new(type_id)?.snapshot());
+        let mut types = self.types.write().unwrap_or_else(PoisonError::into_inner);
+        Ok(Arc::clone(types.entry(type_id.clone()).or_insert(info)))
+    }
+
+    /// Remove all cached entries, e.g. after the installed custom types changed.
+    pub fn clear(&self) {
+        self.types
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+    }
+}
@@ -1,5 +1,7 @@
+use crate::error::UdmfError;
 use std::ffi::{CStr, CString};
 use std::fmt;
+use std::str::FromStr;
 use udmf_sys::meta::*;
 
 /// Unified Data Types (UDT) supported by UDMF.
@@ -151,6 +153,21 @@ impl UniformDataType {
     /// Convert the enum variant to a C string constant.
     pub fn to_cstr(&self) -> &CStr {
         match self {
+            Self::Other(c_s) => c_s.as_c_str(),
+            known => known.static_cstr().expect("known types have a static id"),
+        }
+    }
+
+    /// Get the type id of a known variant, or `None` for [`Other`](Self::Other).
+    ///
+    /// Use [`to_cstr`](Self::to_cstr) or the `Display` implementation to get the id of any type.
+    pub fn as_str(&self) -> Option<&'static str> {
+        self.static_cstr()
+            .map(|id| id.to_str().expect("UDMF type ids are ASCII"))
+    }
+
+    fn static_cstr(&self) -> Option<&'static CStr> {
+        let id = match self {
             Self::Entity => UDMF_META_ENTITY,
             Self::Object => UDMF_META_OBJECT,
             Self::CompositeObject => UDMF_META_COMPOSITE_OBJECT,
@@ -288,8 +305,9 @@ impl UniformDataType {
             Self::OpenHarmonyWant => UDMF_META_OPENHARMONY_WANT,
             Self::GeneralFileUri => UDMF_META_GENERAL_FILE_URI,
             Self::GeneralContentForm => UDMF_METE_GENERAL_CONTENT_FORM,
-            Self::Other(c_s) => c_s.as_c_str(),
-        }
+            Self::Other(_) => return None,
+        };
+        Some(id)
     }
 }
 
@@ -575,8 +593,50 @@ impl From<&CStr> for UniformDataType {
     }
 }
 
+impl TryFrom<&str> for UniformDataType {
+    type Error = UdmfError;
+
+    /// Parse a type id, failing if it contains a NUL byte.
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        let id =
+            CString::new(s).map_err(|_| UdmfError::invalid_param("UniformDataType::try_from"))?;
+        Ok(Self::from(id.as_c_str()))
+    }
+}
+
+impl FromStr for UniformDataType {
+    type Err = UdmfError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s)
+    }
+}
+
 impl fmt::Display for UniformDataType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.to_cstr().to_string_lossy())
+        match self.as_str() {
+            Some(id) => f.write_str(id),
+            None => write!(f, "{}", self.to_cstr().to_string_lossy()),
+        }
+    }
+}
+
+/// Serialized as the canonical type id, e.g. `"general.plain-text"`.
+#[cfg(feature = "serde")]
+impl serde::Serialize for UniformDataType {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let id = self
+            .to_cstr()
+            .to_str()
+            .map_err(<S::Error as serde::ser::Error>::custom)?;
+        serializer.serialize_str(id)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for UniformDataType {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let id = String::deserialize(deserializer)?;
+        Self::try_from(id.as_str()).map_err(<D::Error as serde::de::Error>::custom)
     }
 }
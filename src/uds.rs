@@ -1,13 +1,48 @@
 use crate::error::{Result, UdmfError, to_result};
+use crate::pixel_map::{NativePixelMap, PixelFormat};
+use crate::types::UniformDataType;
+use crate::unified_data::UnifiedRecord;
 use ohos_sys_opaque_types::*;
 use std::ffi::{CStr, CString};
 use udmf_sys::data_struct::*;
 
+pub(crate) mod private {
+    use crate::error::Result;
+    use crate::unified_data::UnifiedRecord;
+
+    /// Keeps [`UdsObject`](super::UdsObject) from being implemented outside this crate and holds
+    /// the record accessors used by [`UnifiedRecord::add`] and [`UnifiedRecord::get`].
+    pub trait Sealed {
+        fn add_to(&self, record: &mut UnifiedRecord) -> Result<()>;
+
+        fn get_from(record: &UnifiedRecord) -> Result<Self>
+        where
+            Self: Sized;
+    }
+}
+
+/// Common interface of the uniform data structures that have a type of their own.
+///
+/// [`ArrayBuffer`] does not implement it, as its type is chosen when it is added to a record.
+/// The structures can be added to and read from records generically with
+/// [`UnifiedRecord::add`] and [`UnifiedRecord::get`].
+pub trait UdsObject: private::Sealed {
+    /// Get the type of the structure as reported by UDMF, e.g. `general.html` for [`Html`].
+    fn type_id(&self) -> UniformDataType;
+}
+
 macro_rules! uds_wrapper {
-    ($name:ident, $raw:ident, $create:ident, $destroy:ident) => {
+    (
+        $name:ident,
+        $raw:ident,
+        $create:ident,
+        $destroy:ident
+        $(; $($field:ident: $field_ty:ty = $field_init:expr),+)?
+    ) => {
         pub struct $name {
             pub(crate) inner: *mut $raw,
             owned: bool,
+            $($(pub(crate) $field: $field_ty,)+)?
         }
 
         impl $name {
@@ -15,9 +50,13 @@ macro_rules! uds_wrapper {
                 // SAFETY: $create() is an FFI function that creates a new UDS instance.
                 let inner = unsafe { $create() };
                 if inner.is_null() {
-                    return Err(UdmfError::InternalError(0));
+                    return Err(UdmfError::null_pointer(stringify!($create)));
                 }
-                Ok(Self { inner, owned: true })
+                Ok(Self {
+                    inner,
+                    owned: true,
+                    $($($field: $field_init,)+)?
+                })
             }
 
             #[allow(dead_code)]
@@ -25,8 +64,15 @@ macro_rules! uds_wrapper {
                 Self {
                     inner,
                     owned: false,
+                    $($($field: $field_init,)+)?
                 }
             }
+
+            /// Release the wrapper and return the raw pointer without destroying it.
+            pub(crate) fn into_raw(self) -> *mut $raw {
+                let this = std::mem::ManuallyDrop::new(self);
+                this.inner
+            }
         }
 
         impl Drop for $name {
@@ -38,13 +84,51 @@ macro_rules! uds_wrapper {
             }
         }
     };
+    (
+        $name:ident,
+        $raw:ident,
+        $create:ident,
+        $destroy:ident,
+        $get_type:ident => $type_id:ident,
+        $add:ident / $get:ident
+        $(; $($field:ident: $field_ty:ty = $field_init:expr),+)?
+    ) => {
+        uds_wrapper!(
+            $name, $raw, $create, $destroy $(; $($field: $field_ty = $field_init),+)?
+        );
+
+        impl private::Sealed for $name {
+            fn add_to(&self, record: &mut UnifiedRecord) -> Result<()> {
+                record.$add(self)
+            }
+
+            fn get_from(record: &UnifiedRecord) -> Result<Self> {
+                record.$get()
+            }
+        }
+
+        impl UdsObject for $name {
+            fn type_id(&self) -> UniformDataType {
+                // SAFETY: self.inner is a valid pointer to $raw.
+                let c_ptr = unsafe { $get_type(self.inner) };
+                if c_ptr.is_null() {
+                    UniformDataType::$type_id
+                } else {
+                    // SAFETY: c_ptr is a valid C string returned by UDMF.
+                    UniformDataType::from(unsafe { CStr::from_ptr(c_ptr) })
+                }
+            }
+        }
+    };
 }
 
 uds_wrapper!(
     PlainText,
     OH_UdsPlainText,
     OH_UdsPlainText_Create,
-    OH_UdsPlainText_Destroy
+    OH_UdsPlainText_Destroy,
+    OH_UdsPlainText_GetType => PlainText,
+    add_plain_text / get_plain_text
 );
 impl PlainText {
     pub fn get_content(&self) -> String {
@@ -65,14 +149,15 @@ impl PlainText {
     }
 
     pub fn set_content(&mut self, content: &str) -> Result<()> {
-        let c_content = CString::new(content).map_err(|_| UdmfError::InvalidParam)?;
+        let c_content = CString::new(content)
+            .map_err(|_| UdmfError::invalid_param("OH_UdsPlainText_SetContent"))?;
         self.set_content_cstr(&c_content)
     }
 
     pub fn set_content_cstr(&mut self, content: &CStr) -> Result<()> {
         // SAFETY: self.inner is a valid pointer and content is a valid C string.
         let res = unsafe { OH_UdsPlainText_SetContent(self.inner, content.as_ptr()) };
-        to_result(res)
+        to_result(res, "OH_UdsPlainText_SetContent")
     }
 
     pub fn get_abstract(&self) -> String {
@@ -93,14 +178,15 @@ impl PlainText {
     }
 
     pub fn set_abstract(&mut self, abstract_text: &str) -> Result<()> {
-        let c_abstract = CString::new(abstract_text).map_err(|_| UdmfError::InvalidParam)?;
+        let c_abstract = CString::new(abstract_text)
+            .map_err(|_| UdmfError::invalid_param("OH_UdsPlainText_SetAbstract"))?;
         self.set_abstract_cstr(&c_abstract)
     }
 
     pub fn set_abstract_cstr(&mut self, abstract_text: &CStr) -> Result<()> {
         // SAFETY: self.inner is a valid pointer and c_abstract is a valid C string.
         let res = unsafe { OH_UdsPlainText_SetAbstract(self.inner, abstract_text.as_ptr()) };
-        to_result(res)
+        to_result(res, "OH_UdsPlainText_SetAbstract")
     }
 }
 
@@ -108,7 +194,9 @@ uds_wrapper!(
     Hyperlink,
     OH_UdsHyperlink,
     OH_UdsHyperlink_Create,
-    OH_UdsHyperlink_Destroy
+    OH_UdsHyperlink_Destroy,
+    OH_UdsHyperlink_GetType => Hyperlink,
+    add_hyperlink / get_hyperlink
 );
 impl Hyperlink {
     pub fn get_url(&self) -> String {
@@ -129,14 +217,15 @@ impl Hyperlink {
     }
 
     pub fn set_url(&mut self, url: &str) -> Result<()> {
-        let c_url = CString::new(url).map_err(|_| UdmfError::InvalidParam)?;
+        let c_url =
+            CString::new(url).map_err(|_| UdmfError::invalid_param("OH_UdsHyperlink_SetUrl"))?;
         self.set_url_cstr(&c_url)
     }
 
     pub fn set_url_cstr(&mut self, url: &CStr) -> Result<()> {
         // SAFETY: self.inner is a valid pointer and c_url is a valid C string.
         let res = unsafe { OH_UdsHyperlink_SetUrl(self.inner, url.as_ptr()) };
-        to_result(res)
+        to_result(res, "OH_UdsHyperlink_SetUrl")
     }
 
     pub fn get_description(&self) -> String {
@@ -157,18 +246,26 @@ impl Hyperlink {
     }
 
     pub fn set_description(&mut self, description: &str) -> Result<()> {
-        let c_desc = CString::new(description).map_err(|_| UdmfError::InvalidParam)?;
+        let c_desc = CString::new(description)
+            .map_err(|_| UdmfError::invalid_param("OH_UdsHyperlink_SetDescription"))?;
         self.set_description_cstr(&c_desc)
     }
 
     pub fn set_description_cstr(&mut self, description: &CStr) -> Result<()> {
         // SAFETY: self.inner is a valid pointer and c_desc is a valid C string.
         let res = unsafe { OH_UdsHyperlink_SetDescription(self.inner, description.as_ptr()) };
-        to_result(res)
+        to_result(res, "OH_UdsHyperlink_SetDescription")
     }
 }
 
-uds_wrapper!(Html, OH_UdsHtml, OH_UdsHtml_Create, OH_UdsHtml_Destroy);
+uds_wrapper!(
+    Html,
+    OH_UdsHtml,
+    OH_UdsHtml_Create,
+    OH_UdsHtml_Destroy,
+    OH_UdsHtml_GetType => Html,
+    add_html / get_html
+);
 impl Html {
     pub fn get_content(&self) -> String {
         self.get_content_cstr()
@@ -188,14 +285,15 @@ impl Html {
     }
 
     pub fn set_content(&mut self, content: &str) -> Result<()> {
-        let c_content = CString::new(content).map_err(|_| UdmfError::InvalidParam)?;
+        let c_content =
+            CString::new(content).map_err(|_| UdmfError::invalid_param("OH_UdsHtml_SetContent"))?;
         self.set_content_cstr(&c_content)
     }
 
     pub fn set_content_cstr(&mut self, content: &CStr) -> Result<()> {
         // SAFETY: self.inner is a valid pointer and c_content is a valid C string.
         let res = unsafe { OH_UdsHtml_SetContent(self.inner, content.as_ptr()) };
-        to_result(res)
+        to_result(res, "OH_UdsHtml_SetContent")
     }
 
     pub fn get_plain_content(&self) -> String {
@@ -216,14 +314,15 @@ impl Html {
     }
 
     pub fn set_plain_content(&mut self, plain_content: &str) -> Result<()> {
-        let c_plain = CString::new(plain_content).map_err(|_| UdmfError::InvalidParam)?;
+        let c_plain = CString::new(plain_content)
+            .map_err(|_| UdmfError::invalid_param("OH_UdsHtml_SetPlainContent"))?;
         self.set_plain_content_cstr(&c_plain)
     }
 
     pub fn set_plain_content_cstr(&mut self, plain_content: &CStr) -> Result<()> {
         // SAFETY: self.inner is a valid pointer and c_plain is a valid C string.
         let res = unsafe { OH_UdsHtml_SetPlainContent(self.inner, plain_content.as_ptr()) };
-        to_result(res)
+        to_result(res, "OH_UdsHtml_SetPlainContent")
     }
 }
 
@@ -231,10 +330,127 @@ uds_wrapper!(
     AppItem,
     OH_UdsAppItem,
     OH_UdsAppItem_Create,
-    OH_UdsAppItem_Destroy
+    OH_UdsAppItem_Destroy,
+    OH_UdsAppItem_GetType => OpenHarmonyAppItem,
+    add_app_item / get_app_item
 );
-// Simplified AppItem implementation, focusing on bundle name and ability name
+// UDMF's app item also has a description, but the C API has no accessor for it.
 impl AppItem {
+    pub fn get_id(&self) -> String {
+        self.get_id_cstr()
+            .map(|c| c.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    }
+
+    pub fn get_id_cstr(&self) -> Option<&CStr> {
+        // SAFETY: self.inner is a valid pointer to OH_UdsAppItem.
+        let c_ptr = unsafe { OH_UdsAppItem_GetId(self.inner) };
+        if c_ptr.is_null() {
+            None
+        } else {
+            // SAFETY: c_ptr is a valid C string returned by UDMF and it is valid as long as self is alive.
+            Some(unsafe { CStr::from_ptr(c_ptr) })
+        }
+    }
+
+    pub fn set_id(&mut self, id: &str) -> Result<()> {
+        let c_id = CString::new(id).map_err(|_| UdmfError::invalid_param("OH_UdsAppItem_SetId"))?;
+        self.set_id_cstr(&c_id)
+    }
+
+    pub fn set_id_cstr(&mut self, id: &CStr) -> Result<()> {
+        // SAFETY: self.inner is a valid pointer to OH_UdsAppItem, and id is a valid C string.
+        let res = unsafe { OH_UdsAppItem_SetId(self.inner, id.as_ptr()) };
+        to_result(res, "OH_UdsAppItem_SetId")
+    }
+
+    pub fn get_name(&self) -> String {
+        self.get_name_cstr()
+            .map(|c| c.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    }
+
+    pub fn get_name_cstr(&self) -> Option<&CStr> {
+        // SAFETY: self.inner is a valid pointer to OH_UdsAppItem.
+        let c_ptr = unsafe { OH_UdsAppItem_GetName(self.inner) };
+        if c_ptr.is_null() {
+            None
+        } else {
+            // SAFETY: c_ptr is a valid C string returned by UDMF and it is valid as long as self is alive.
+            Some(unsafe { CStr::from_ptr(c_ptr) })
+        }
+    }
+
+    pub fn set_name(&mut self, name: &str) -> Result<()> {
+        let c_name =
+            CString::new(name).map_err(|_| UdmfError::invalid_param("OH_UdsAppItem_SetName"))?;
+        self.set_name_cstr(&c_name)
+    }
+
+    pub fn set_name_cstr(&mut self, name: &CStr) -> Result<()> {
+        // SAFETY: self.inner is a valid pointer to OH_UdsAppItem, and name is a valid C string.
+        let res = unsafe { OH_UdsAppItem_SetName(self.inner, name.as_ptr()) };
+        to_result(res, "OH_UdsAppItem_SetName")
+    }
+
+    pub fn get_icon_id(&self) -> String {
+        self.get_icon_id_cstr()
+            .map(|c| c.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    }
+
+    pub fn get_icon_id_cstr(&self) -> Option<&CStr> {
+        // SAFETY: self.inner is a valid pointer to OH_UdsAppItem.
+        let c_ptr = unsafe { OH_UdsAppItem_GetIconId(self.inner) };
+        if c_ptr.is_null() {
+            None
+        } else {
+            // SAFETY: c_ptr is a valid C string returned by UDMF and it is valid as long as self is alive.
+            Some(unsafe { CStr::from_ptr(c_ptr) })
+        }
+    }
+
+    pub fn set_icon_id(&mut self, id: &str) -> Result<()> {
+        let c_id =
+            CString::new(id).map_err(|_| UdmfError::invalid_param("OH_UdsAppItem_SetIconId"))?;
+        self.set_icon_id_cstr(&c_id)
+    }
+
+    pub fn set_icon_id_cstr(&mut self, id: &CStr) -> Result<()> {
+        // SAFETY: self.inner is a valid pointer to OH_UdsAppItem, and id is a valid C string.
+        let res = unsafe { OH_UdsAppItem_SetIconId(self.inner, id.as_ptr()) };
+        to_result(res, "OH_UdsAppItem_SetIconId")
+    }
+
+    pub fn get_label_id(&self) -> String {
+        self.get_label_id_cstr()
+            .map(|c| c.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    }
+
+    pub fn get_label_id_cstr(&self) -> Option<&CStr> {
+        // SAFETY: self.inner is a valid pointer to OH_UdsAppItem.
+        let c_ptr = unsafe { OH_UdsAppItem_GetLabelId(self.inner) };
+        if c_ptr.is_null() {
+            None
+        } else {
+            // SAFETY: c_ptr is a valid C string returned by UDMF and it is valid as long as self is alive.
+            Some(unsafe { CStr::from_ptr(c_ptr) })
+        }
+    }
+
+    pub fn set_label_id(&mut self, id: &str) -> Result<()> {
+        let c_id =
+            CString::new(id).map_err(|_| UdmfError::invalid_param("OH_UdsAppItem_SetLabelId"))?;
+        self.set_label_id_cstr(&c_id)
+    }
+
+    pub fn set_label_id_cstr(&mut self, id: &CStr) -> Result<()> {
+        // SAFETY: self.inner is a valid pointer to OH_UdsAppItem, and id is a valid C string.
+        let res = unsafe { OH_UdsAppItem_SetLabelId(self.inner, id.as_ptr()) };
+        to_result(res, "OH_UdsAppItem_SetLabelId")
+    }
+
     pub fn get_bundle_name(&self) -> String {
         self.get_bundle_name_cstr()
             .map(|c| c.to_string_lossy().into_owned())
@@ -253,14 +469,15 @@ impl AppItem {
     }
 
     pub fn set_bundle_name(&mut self, name: &str) -> Result<()> {
-        let c_name = CString::new(name).map_err(|_| UdmfError::InvalidParam)?;
+        let c_name = CString::new(name)
+            .map_err(|_| UdmfError::invalid_param("OH_UdsAppItem_SetBundleName"))?;
         self.set_bundle_name_cstr(&c_name)
     }
 
     pub fn set_bundle_name_cstr(&mut self, name: &CStr) -> Result<()> {
         // SAFETY: self.inner is a valid pointer and c_name is a valid C string.
         let res = unsafe { OH_UdsAppItem_SetBundleName(self.inner, name.as_ptr()) };
-        to_result(res)
+        to_result(res, "OH_UdsAppItem_SetBundleName")
     }
 
     pub fn get_ability_name(&self) -> String {
@@ -281,14 +498,15 @@ impl AppItem {
     }
 
     pub fn set_ability_name(&mut self, name: &str) -> Result<()> {
-        let c_name = CString::new(name).map_err(|_| UdmfError::InvalidParam)?;
+        let c_name = CString::new(name)
+            .map_err(|_| UdmfError::invalid_param("OH_UdsAppItem_SetAbilityName"))?;
         self.set_ability_name_cstr(&c_name)
     }
 
     pub fn set_ability_name_cstr(&mut self, name: &CStr) -> Result<()> {
         // SAFETY: self.inner is a valid pointer to OH_UdsAppItem, and c_name is a valid C string.
         let res = unsafe { OH_UdsAppItem_SetAbilityName(self.inner, name.as_ptr()) };
-        to_result(res)
+        to_result(res, "OH_UdsAppItem_SetAbilityName")
     }
 }
 
@@ -296,7 +514,9 @@ uds_wrapper!(
     FileUri,
     OH_UdsFileUri,
     OH_UdsFileUri_Create,
-    OH_UdsFileUri_Destroy
+    OH_UdsFileUri_Destroy,
+    OH_UdsFileUri_GetType => GeneralFileUri,
+    add_file_uri / get_file_uri
 );
 impl FileUri {
     pub fn get_file_uri(&self) -> String {
@@ -317,14 +537,15 @@ impl FileUri {
     }
 
     pub fn set_file_uri(&mut self, uri: &str) -> Result<()> {
-        let c_uri = CString::new(uri).map_err(|_| UdmfError::InvalidParam)?;
+        let c_uri =
+            CString::new(uri).map_err(|_| UdmfError::invalid_param("OH_UdsFileUri_SetFileUri"))?;
         self.set_file_uri_cstr(&c_uri)
     }
 
     pub fn set_file_uri_cstr(&mut self, uri: &CStr) -> Result<()> {
         // SAFETY: self.inner is a valid pointer to OH_UdsFileUri, and c_uri is a valid C string.
         let res = unsafe { OH_UdsFileUri_SetFileUri(self.inner, uri.as_ptr()) };
-        to_result(res)
+        to_result(res, "OH_UdsFileUri_SetFileUri")
     }
 
     pub fn get_file_type(&self) -> String {
@@ -345,14 +566,15 @@ impl FileUri {
     }
 
     pub fn set_file_type(&mut self, file_type: &str) -> Result<()> {
-        let c_type = CString::new(file_type).map_err(|_| UdmfError::InvalidParam)?;
+        let c_type = CString::new(file_type)
+            .map_err(|_| UdmfError::invalid_param("OH_UdsFileUri_SetFileType"))?;
         self.set_file_type_cstr(&c_type)
     }
 
     pub fn set_file_type_cstr(&mut self, file_type: &CStr) -> Result<()> {
         // SAFETY: self.inner is a valid pointer to OH_UdsFileUri, and c_type is a valid C string.
         let res = unsafe { OH_UdsFileUri_SetFileType(self.inner, file_type.as_ptr()) };
-        to_result(res)
+        to_result(res, "OH_UdsFileUri_SetFileType")
     }
 }
 
@@ -360,43 +582,117 @@ uds_wrapper!(
     PixelMap,
     OH_UdsPixelMap,
     OH_UdsPixelMap_Create,
-    OH_UdsPixelMap_Destroy
+    OH_UdsPixelMap_Destroy,
+    OH_UdsPixelMap_GetType => OpenHarmonyPixelMap,
+    add_pixel_map / get_pixel_map;
+    // UDMF can not tell whether a pixel map was set, so the wrapper keeps track of it.
+    has_pixel_map: bool = false
 );
 impl PixelMap {
-    /// Get the pixel map from the UDS structure.
-    /// Note: This returns a raw pointer to OH_PixelmapNative as we don't have a safe wrapper for it yet.
-    ///
-    /// # Safety
+    /// Get the pixel map stored in the UDS structure, or `None` if none was set.
     ///
-    /// The caller is responsible for the lifetime of the returned pointer.
-    pub unsafe fn get_pixel_map(&self) -> *mut OH_PixelmapNative {
-        let mut pixel_map = std::ptr::null_mut();
-        // SAFETY: self.inner is a valid pointer to OH_UdsPixelMap.
-        // The FFI function expects a pointer to a pointer to OH_PixelmapNative.
-        // We provide a mutable reference to our `pixel_map` variable, which is a `*mut OH_PixelmapNative`,
-        // and cast its address to `*mut *mut OH_PixelmapNative` to match the FFI signature.
-        // The final cast to `*mut OH_PixelmapNative` is likely a workaround for an FFI signature
-        // that might be declared as `*mut OH_PixelmapNative` but expects a `**mut OH_PixelmapNative`
-        // for an output parameter. This is common in C APIs where `void**` is used for generic output.
-        unsafe {
-            OH_UdsPixelMap_GetPixelMap(
-                self.inner,
-                &mut pixel_map as *mut *mut OH_PixelmapNative as *mut OH_PixelmapNative,
-            )
-        };
-        pixel_map
+    /// A pixel map is present after [`set_pixel_map`](Self::set_pixel_map) and on a UDS read
+    /// from a record with [`UnifiedRecord::get_pixel_map`].
+    pub fn get_pixel_map(&self) -> Result<Option<NativePixelMap<'static>>> {
+        if !self.has_pixel_map {
+            return Ok(None);
+        }
+        // UDMF points a pixel map provided by the caller at the stored one.
+        let pixel_map = NativePixelMap::new(1, 1, PixelFormat::Alpha8, &[0])?;
+        // SAFETY: self.inner is a valid pointer to OH_UdsPixelMap and pixel_map is a valid
+        // pixel map that UDMF fills with the stored one.
+        unsafe { OH_UdsPixelMap_GetPixelMap(self.inner, pixel_map.as_ptr()) };
+        Ok(Some(pixel_map))
     }
 
     /// Set the pixel map in the UDS structure.
     ///
-    /// # Safety
+    /// UDMF keeps its own reference, so `pixel_map` may be dropped afterwards.
+    pub fn set_pixel_map(&mut self, pixel_map: &NativePixelMap<'_>) -> Result<()> {
+        // SAFETY: self.inner is a valid pointer to OH_UdsPixelMap and pixel_map is a valid
+        // pixel map.
+        let res = unsafe { OH_UdsPixelMap_SetPixelMap(self.inner, pixel_map.as_ptr()) };
+        to_result(res, "OH_UdsPixelMap_SetPixelMap")?;
+        self.has_pixel_map = true;
+        Ok(())
+    }
+
+    /// Create a UDS holding a pixel map with a copy of `data`.
     ///
-    /// The caller must ensure that `pixel_map` is a valid pointer to an `OH_PixelmapNative` instance.
-    pub unsafe fn set_pixel_map(&mut self, pixel_map: *mut OH_PixelmapNative) -> Result<()> {
-        // SAFETY: self.inner is a valid pointer to OH_UdsPixelMap.
-        // pixel_map is assumed to be valid as per the function's safety requirements.
-        let res = unsafe { OH_UdsPixelMap_SetPixelMap(self.inner, pixel_map) };
-        to_result(res)
+    /// `data` holds `width` x `height` RGBA pixels with 8 bits per channel, row by row and
+    /// without padding.
+    pub fn from_rgba(width: u32, height: u32, data: &[u8]) -> Result<Self> {
+        let len = (width as usize)
+            .checked_mul(height as usize)
+            .and_then(|pixels| pixels.checked_mul(4));
+        if width == 0 || height == 0 || len != Some(data.len()) {
+            return Err(UdmfError::invalid_param("PixelMap::from_rgba"));
+        }
+        let pixel_map = NativePixelMap::new(width, height, PixelFormat::Rgba8888, data)?;
+        let mut uds = Self::new()?;
+        uds.set_pixel_map(&pixel_map)?;
+        Ok(uds)
+    }
+
+    /// Copy the pixel map as `(width, height, pixels)`, in the layout taken by
+    /// [`from_rgba`](Self::from_rgba).
+    ///
+    /// Fails with [`UdmfError::TypeNotFound`] if no pixel map was set. Only pixel maps in
+    /// [`PixelFormat::Rgba8888`] and [`PixelFormat::Bgra8888`] can be converted, other formats
+    /// are rejected as an invalid parameter.
+    pub fn to_rgba(&self) -> Result<(u32, u32, Vec<u8>)> {
+        let pixel_map = self.get_pixel_map()?.ok_or(UdmfError::type_not_found(
+            "OH_UdsPixelMap_GetPixelMap",
+            &UniformDataType::OpenHarmonyPixelMap,
+        ))?;
+        let info = pixel_map.get_image_info()?;
+        if !matches!(
+            info.pixel_format,
+            PixelFormat::Rgba8888 | PixelFormat::Bgra8888
+        ) {
+            return Err(UdmfError::invalid_param("PixelMap::to_rgba"));
+        }
+        let pixels = pixel_map.read_pixels()?;
+        let row_len = info.width as usize * 4;
+        let stride = info.row_stride as usize;
+        let mut rgba = Vec::with_capacity(row_len * info.height as usize);
+        for row in 0..info.height as usize {
+            let start = row * stride;
+            let row = pixels
+                .get(start..start + row_len)
+                .filter(|_| stride >= row_len)
+                .ok_or(UdmfError::invalid_param("PixelMap::to_rgba"))?;
+            rgba.extend_from_slice(row);
+        }
+        if info.pixel_format == PixelFormat::Bgra8888 {
+            for pixel in rgba.chunks_exact_mut(4) {
+                pixel.swap(0, 2);
+            }
+        }
+        Ok((info.width, info.height, rgba))
+    }
+}
+
+#[cfg(feature = "image")]
+impl TryFrom<&image::DynamicImage> for PixelMap {
+    type Error = UdmfError;
+
+    /// Convert the image to RGBA with 8 bits per channel and copy it into a new UDS.
+    fn try_from(image: &image::DynamicImage) -> Result<Self> {
+        let rgba = image.to_rgba8();
+        Self::from_rgba(rgba.width(), rgba.height(), rgba.as_raw())
+    }
+}
+
+#[cfg(feature = "image")]
+impl TryFrom<&PixelMap> for image::DynamicImage {
+    type Error = UdmfError;
+
+    fn try_from(pixel_map: &PixelMap) -> Result<Self> {
+        let (width, height, data) = pixel_map.to_rgba()?;
+        image::RgbaImage::from_raw(width, height, data)
+            .map(Self::ImageRgba8)
+            .ok_or(UdmfError::invalid_param("DynamicImage::try_from"))
     }
 }
 
@@ -413,7 +709,7 @@ impl ArrayBuffer {
         // SAFETY: self.inner is a valid pointer to OH_UdsArrayBuffer.
         // data_ptr and len are pointers to values that will be initialized by the FFI call.
         let res = unsafe { OH_UdsArrayBuffer_GetData(self.inner, &mut data_ptr, &mut len) };
-        to_result(res)?;
+        to_result(res, "OH_UdsArrayBuffer_GetData")?;
         if data_ptr.is_null() || len == 0 {
             return Ok(Vec::new());
         }
@@ -429,7 +725,7 @@ impl ArrayBuffer {
         let res = unsafe {
             OH_UdsArrayBuffer_SetData(self.inner, data.as_ptr() as *mut u8, data.len() as u32)
         };
-        to_result(res)
+        to_result(res, "OH_UdsArrayBuffer_SetData")
     }
 }
 
@@ -437,7 +733,9 @@ uds_wrapper!(
     ContentForm,
     OH_UdsContentForm,
     OH_UdsContentForm_Create,
-    OH_UdsContentForm_Destroy
+    OH_UdsContentForm_Destroy,
+    OH_UdsContentForm_GetType => GeneralContentForm,
+    add_content_form / get_content_form
 );
 impl ContentForm {
     pub fn get_title(&self) -> String {
@@ -458,14 +756,15 @@ impl ContentForm {
     }
 
     pub fn set_title(&mut self, title: &str) -> Result<()> {
-        let c_title = CString::new(title).map_err(|_| UdmfError::InvalidParam)?;
+        let c_title = CString::new(title)
+            .map_err(|_| UdmfError::invalid_param("OH_UdsContentForm_SetTitle"))?;
         self.set_title_cstr(&c_title)
     }
 
     pub fn set_title_cstr(&mut self, title: &CStr) -> Result<()> {
         // SAFETY: self.inner is a valid pointer to OH_UdsContentForm, and c_title is a valid C string.
         let res = unsafe { OH_UdsContentForm_SetTitle(self.inner, title.as_ptr()) };
-        to_result(res)
+        to_result(res, "OH_UdsContentForm_SetTitle")
     }
 
     pub fn get_description(&self) -> String {
@@ -486,14 +785,15 @@ impl ContentForm {
     }
 
     pub fn set_description(&mut self, description: &str) -> Result<()> {
-        let c_desc = CString::new(description).map_err(|_| UdmfError::InvalidParam)?;
+        let c_desc = CString::new(description)
+            .map_err(|_| UdmfError::invalid_param("OH_UdsContentForm_SetDescription"))?;
         self.set_description_cstr(&c_desc)
     }
 
     pub fn set_description_cstr(&mut self, description: &CStr) -> Result<()> {
         // SAFETY: self.inner is a valid pointer to OH_UdsContentForm, and c_desc is a valid C string.
         let res = unsafe { OH_UdsContentForm_SetDescription(self.inner, description.as_ptr()) };
-        to_result(res)
+        to_result(res, "OH_UdsContentForm_SetDescription")
     }
 
     pub fn get_app_name(&self) -> String {
@@ -514,14 +814,15 @@ impl ContentForm {
     }
 
     pub fn set_app_name(&mut self, name: &str) -> Result<()> {
-        let c_name = CString::new(name).map_err(|_| UdmfError::InvalidParam)?;
+        let c_name = CString::new(name)
+            .map_err(|_| UdmfError::invalid_param("OH_UdsContentForm_SetAppName"))?;
         self.set_app_name_cstr(&c_name)
     }
 
     pub fn set_app_name_cstr(&mut self, name: &CStr) -> Result<()> {
         // SAFETY: self.inner is a valid pointer to OH_UdsContentForm, and c_name is a valid C string.
         let res = unsafe { OH_UdsContentForm_SetAppName(self.inner, name.as_ptr()) };
-        to_result(res)
+        to_result(res, "OH_UdsContentForm_SetAppName")
     }
 
     pub fn get_link_uri(&self) -> String {
@@ -542,14 +843,15 @@ impl ContentForm {
     }
 
     pub fn set_link_uri(&mut self, uri: &str) -> Result<()> {
-        let c_uri = CString::new(uri).map_err(|_| UdmfError::InvalidParam)?;
+        let c_uri = CString::new(uri)
+            .map_err(|_| UdmfError::invalid_param("OH_UdsContentForm_SetLinkUri"))?;
         self.set_link_uri_cstr(&c_uri)
     }
 
     pub fn set_link_uri_cstr(&mut self, uri: &CStr) -> Result<()> {
         // SAFETY: self.inner is a valid pointer to OH_UdsContentForm, and c_uri is a valid C string.
         let res = unsafe { OH_UdsContentForm_SetLinkUri(self.inner, uri.as_ptr()) };
-        to_result(res)
+        to_result(res, "OH_UdsContentForm_SetLinkUri")
     }
 
     pub fn get_thumb_data(&self) -> Result<Vec<u8>> {
@@ -558,7 +860,7 @@ impl ContentForm {
         // SAFETY: self.inner is a valid pointer to OH_UdsContentForm.
         // data_ptr and len are pointers to values that will be initialized by the FFI call.
         let res = unsafe { OH_UdsContentForm_GetThumbData(self.inner, &mut data_ptr, &mut len) };
-        to_result(res)?;
+        to_result(res, "OH_UdsContentForm_GetThumbData")?;
         if data_ptr.is_null() || len == 0 {
             return Ok(Vec::new());
         }
@@ -572,7 +874,7 @@ impl ContentForm {
         // data.as_ptr() and data.len() represent a valid slice of memory.
         let res =
             unsafe { OH_UdsContentForm_SetThumbData(self.inner, data.as_ptr(), data.len() as u32) };
-        to_result(res)
+        to_result(res, "OH_UdsContentForm_SetThumbData")
     }
 
     pub fn get_app_icon(&self) -> Result<Vec<u8>> {
@@ -581,7 +883,7 @@ impl ContentForm {
         // SAFETY: self.inner is a valid pointer to OH_UdsContentForm.
         // data_ptr and len are pointers to values that will be initialized by the FFI call.
         let res = unsafe { OH_UdsContentForm_GetAppIcon(self.inner, &mut data_ptr, &mut len) };
-        to_result(res)?;
+        to_result(res, "OH_UdsContentForm_GetAppIcon")?;
         if data_ptr.is_null() || len == 0 {
             return Ok(Vec::new());
         }
@@ -595,6 +897,6 @@ impl ContentForm {
         // data.as_ptr() and data.len() represent a valid slice of memory.
         let res =
             unsafe { OH_UdsContentForm_SetAppIcon(self.inner, data.as_ptr(), data.len() as u32) };
-        to_result(res)
+        to_result(res, "OH_UdsContentForm_SetAppIcon")
     }
 }
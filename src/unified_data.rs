@@ -1,7 +1,13 @@
 use crate::UniformDataType;
 use crate::error::{Result, UdmfError, to_result};
+use crate::properties::UnifiedDataProperties;
+use crate::type_cache::TypeCache;
+use crate::uds::UdsObject;
 use ohos_sys_opaque_types::*;
+use std::collections::HashMap;
 use std::ffi::CStr;
+use std::marker::PhantomData;
+use std::ops::Deref;
 use std::ptr;
 use udmf_sys::data_management_framework::*;
 
@@ -15,7 +21,7 @@ impl UnifiedData {
         // SAFETY: OH_UdmfData_Create creates a new instance of UnifiedData.
         let inner = unsafe { OH_UdmfData_Create() };
         if inner.is_null() {
-            return Err(UdmfError::InternalError(0));
+            return Err(UdmfError::null_pointer("OH_UdmfData_Create"));
         }
         Ok(Self { inner, owned: true })
     }
@@ -40,7 +46,12 @@ impl UnifiedData {
     pub fn add_record(&mut self, record: &UnifiedRecord) -> Result<()> {
         // SAFETY: self.inner and record.inner are valid pointers to UdmfData and UdmfRecord respectively.
         let res = unsafe { OH_UdmfData_AddRecord(self.inner, record.inner) };
-        to_result(res)
+        to_result(res, "OH_UdmfData_AddRecord")
+    }
+
+    /// Get the properties of this data, e.g. to restrict its share option.
+    pub fn get_properties(&self) -> Result<UnifiedDataProperties> {
+        UnifiedDataProperties::new(self)
     }
 
     pub fn has_type(&self, type_id: &UniformDataType) -> bool {
@@ -70,7 +81,10 @@ impl UnifiedData {
         types
     }
 
-    pub fn get_records(&self) -> Vec<UnifiedRecord> {
+    /// Get all records of this data.
+    ///
+    /// The records are owned by the `UnifiedData` and borrow from it.
+    pub fn get_records(&self) -> Vec<RecordRef<'_>> {
         let mut count: u32 = 0;
         // SAFETY: self.inner is a valid pointer. count will be initialized by the FFI call.
         let records_ptr = unsafe { OH_UdmfData_GetRecords(self.inner, &mut count) };
@@ -83,13 +97,176 @@ impl UnifiedData {
             // SAFETY: records_ptr is a valid pointer returned by UDMF and count is correct.
             let r_ptr = unsafe { *records_ptr.add(i as usize) };
             if !r_ptr.is_null() {
-                // These records are belonging to the UnifiedData, so they are not owned by the wrapper
-                // SAFETY: r_ptr is a valid pointer to OH_UdmfRecord.
-                records.push(unsafe { UnifiedRecord::from_ptr(r_ptr, false) });
+                // SAFETY: r_ptr is a valid pointer to OH_UdmfRecord belonging to self.
+                records.push(unsafe { RecordRef::from_ptr(r_ptr) });
             }
         }
         records
     }
+
+    /// Get the number of records in this data.
+    #[doc(alias = "record_count")]
+    pub fn get_record_count(&self) -> usize {
+        // SAFETY: self.inner is a valid pointer.
+        let count = unsafe { OH_UdmfData_GetRecordCount(self.inner) };
+        usize::try_from(count).unwrap_or(0)
+    }
+
+    /// Get the record at `index`, or `None` if the index is out of bounds.
+    #[doc(alias = "record")]
+    pub fn get_record(&self, index: usize) -> Option<RecordRef<'_>> {
+        let index = u32::try_from(index).ok()?;
+        // SAFETY: self.inner is a valid pointer.
+        let r_ptr = unsafe { OH_UdmfData_GetRecord(self.inner, index) };
+        if r_ptr.is_null() {
+            return None;
+        }
+        // SAFETY: r_ptr is a valid pointer to OH_UdmfRecord belonging to self.
+        Some(unsafe { RecordRef::from_ptr(r_ptr) })
+    }
+
+    /// Copy this data, which may be borrowed from UDMF, into new owned data.
+    ///
+    /// The records, the tag and the share option are copied. The timestamp is set by UDMF and
+    /// the extras can not be listed, so neither is carried over.
+    #[cfg(feature = "api-15")]
+    pub(crate) fn copy(&self) -> Result<UnifiedData> {
+        let mut copy = UnifiedData::new()?;
+        for record in self.get_records() {
+            copy.add_record(&record)?;
+        }
+        let properties = self.get_properties()?;
+        let mut copied = copy.get_properties()?;
+        if let Some(tag) = properties.get_tag_cstr().filter(|tag| !tag.is_empty()) {
+            copied.set_tag_cstr(tag)?;
+        }
+        if let Some(option) = properties.get_share_option() {
+            copied.set_share_option(option)?;
+        }
+        Ok(copy)
+    }
+
+    /// Get the first plain text entry of this data.
+    #[doc(alias = "primary_plain_text")]
+    pub fn get_primary_plain_text(&self) -> Result<crate::uds::PlainText> {
+        let plain_text = crate::uds::PlainText::new()?;
+        // SAFETY: self.inner and plain_text.inner are valid pointers.
+        let res = unsafe { OH_UdmfData_GetPrimaryPlainText(self.inner, plain_text.inner) };
+        to_result(res, "OH_UdmfData_GetPrimaryPlainText").map_err(|e| {
+            e.with_type_id(&UniformDataType::PlainText)
+                .or_type_not_found(|| self.has_type(&UniformDataType::PlainText))
+        })?;
+        Ok(plain_text)
+    }
+
+    /// Get the first html entry of this data.
+    #[doc(alias = "primary_html")]
+    pub fn get_primary_html(&self) -> Result<crate::uds::Html> {
+        let html = crate::uds::Html::new()?;
+        // SAFETY: self.inner and html.inner are valid pointers.
+        let res = unsafe { OH_UdmfData_GetPrimaryHtml(self.inner, html.inner) };
+        to_result(res, "OH_UdmfData_GetPrimaryHtml").map_err(|e| {
+            e.with_type_id(&UniformDataType::Html)
+                .or_type_not_found(|| self.has_type(&UniformDataType::Html))
+        })?;
+        Ok(html)
+    }
+
+    /// Pick the record and entry type that best match the types a consumer accepts.
+    ///
+    /// `accepted` is ordered by preference. An entry whose type is accepted exactly is
+    /// preferred, taking the first accepted type that any record provides. Otherwise the entry
+    /// whose type is closest below an accepted type in the UTD hierarchy is chosen, e.g. a
+    /// `general.png` entry for a consumer of `general.image`. For these matches the distance in
+    /// the hierarchy outranks the preference: an entry one level below a less preferred type
+    /// wins over an entry two levels below a more preferred one. Preference and then record
+    /// order only break ties.
+    ///
+    /// The hierarchy is resolved on the platform through [`TypeCache::global`], so it agrees
+    /// with [`TypeDescriptor::belongs_to`](crate::utd::TypeDescriptor::belongs_to).
+    ///
+    /// The returned type is the type of the entry in the record, which may be more specific
+    /// than the accepted type.
+    pub fn negotiate(
+        &self,
+        accepted: &[UniformDataType],
+    ) -> Option<(RecordRef<'_>, UniformDataType)> {
+        let records: Vec<_> = self
+            .get_records()
+            .into_iter()
+            .map(|record| {
+                let types = record.get_types();
+                (record, types)
+            })
+            .collect();
+
+        let exact = accepted.iter().find_map(|type_id| {
+            records
+                .iter()
+                .position(|(_, types)| types.contains(type_id))
+                .map(|index| (index, type_id.clone()))
+        });
+        // Rank candidates by distance to the accepted type, then by preference and position.
+        let mut ancestors = HashMap::new();
+        let (index, type_id) = exact.or_else(|| {
+            records
+                .iter()
+                .enumerate()
+                .flat_map(|(index, (_, types))| types.iter().map(move |t| (index, t)))
+                .filter_map(|(index, type_id)| {
+                    let depths = ancestors
+                        .entry(type_id)
+                        .or_insert_with(|| ancestor_depths(type_id));
+                    accepted
+                        .iter()
+                        .enumerate()
+                        .filter_map(|(preference, accepted_type)| {
+                            let distance = *depths.get(accepted_type)?;
+                            Some(((distance, preference, index), type_id))
+                        })
+                        .min_by_key(|(rank, _)| *rank)
+                })
+                .min_by_key(|(rank, _)| *rank)
+                .map(|((_, _, index), type_id)| (index, type_id.clone()))
+        })?;
+        let (record, _) = records.into_iter().nth(index)?;
+        Some((record, type_id))
+    }
+
+    /// Returns `true` if the data originates from the local device.
+    pub fn is_local(&self) -> bool {
+        // SAFETY: self.inner is a valid pointer.
+        unsafe { OH_UdmfData_IsLocal(self.inner) }
+    }
+}
+
+/// Get the ancestors of `type_id` together with their depth in the UTD hierarchy, 1 for the
+/// direct parents.
+///
+/// The parents are looked up through the global [`TypeCache`], so each type is resolved on the
+/// platform only once. Types unknown to the platform have no ancestors.
+fn ancestor_depths(type_id: &UniformDataType) -> HashMap<UniformDataType, usize> {
+    let parents = |type_id: &UniformDataType| -> Vec<UniformDataType> {
+        TypeCache::global()
+            .get(type_id)
+            .map(|info| info.belonging_to_types.clone())
+            .unwrap_or_default()
+    };
+    let mut depths = HashMap::new();
+    let mut layer = vec![type_id.clone()];
+    let mut depth = 0;
+    while !layer.is_empty() {
+        depth += 1;
+        let mut next = Vec::new();
+        for parent in layer.iter().flat_map(parents) {
+            if &parent != type_id && !depths.contains_key(&parent) {
+                depths.insert(parent.clone(), depth);
+                next.push(parent);
+            }
+        }
+        layer = next;
+    }
+    depths
 }
 
 impl Drop for UnifiedData {
@@ -111,7 +288,7 @@ impl UnifiedRecord {
         // SAFETY: OH_UdmfRecord_Create creates a new instance of UdmfRecord.
         let inner = unsafe { OH_UdmfRecord_Create() };
         if inner.is_null() {
-            return Err(UdmfError::InternalError(0));
+            return Err(UdmfError::null_pointer("OH_UdmfRecord_Create"));
         }
         Ok(Self { inner, owned: true })
     }
@@ -144,6 +321,19 @@ impl UnifiedRecord {
         types
     }
 
+    /// Convert the status of reading `type_id`, telling a missing type apart from other errors.
+    fn get_result(
+        &self,
+        res: i32,
+        operation: &'static str,
+        type_id: &UniformDataType,
+    ) -> Result<()> {
+        to_result(res, operation).map_err(|e| {
+            e.with_type_id(type_id)
+                .or_type_not_found(|| self.get_types().contains(type_id))
+        })
+    }
+
     pub fn add_general_entry(&mut self, type_id: &UniformDataType, data: &[u8]) -> Result<()> {
         // SAFETY: self.inner and type_id.to_cstr() are valid pointers.
         let res = unsafe {
@@ -154,7 +344,7 @@ impl UnifiedRecord {
                 data.len() as u32,
             )
         };
-        to_result(res)
+        to_result(res, "OH_UdmfRecord_AddGeneralEntry").map_err(|e| e.with_type_id(type_id))
     }
 
     pub fn get_general_entry(&self, type_id: &UniformDataType) -> Result<Vec<u8>> {
@@ -169,7 +359,7 @@ impl UnifiedRecord {
                 &mut count,
             )
         };
-        to_result(res)?;
+        self.get_result(res, "OH_UdmfRecord_GetGeneralEntry", type_id)?;
 
         if data_ptr.is_null() || count == 0 {
             return Ok(Vec::new());
@@ -180,87 +370,123 @@ impl UnifiedRecord {
         Ok(data.to_vec())
     }
 
+    /// Add `uds` as an entry of its own type, e.g. `record.add(&html)`.
+    pub fn add<T: UdsObject>(&mut self, uds: &T) -> Result<()> {
+        uds.add_to(self)
+    }
+
+    /// Get the entry of type `T`, e.g. `record.get::<Html>()`.
+    pub fn get<T: UdsObject>(&self) -> Result<T> {
+        T::get_from(self)
+    }
+
     pub fn add_plain_text(&mut self, plain_text: &crate::uds::PlainText) -> Result<()> {
         // SAFETY: self.inner and plain_text.inner are valid pointers.
         let res = unsafe { OH_UdmfRecord_AddPlainText(self.inner, plain_text.inner) };
-        to_result(res)
+        to_result(res, "OH_UdmfRecord_AddPlainText")
+            .map_err(|e| e.with_type_id(&UniformDataType::PlainText))
     }
 
     pub fn get_plain_text(&self) -> Result<crate::uds::PlainText> {
         let plain_text = crate::uds::PlainText::new()?;
         // SAFETY: self.inner and plain_text.inner are valid pointers.
         let res = unsafe { OH_UdmfRecord_GetPlainText(self.inner, plain_text.inner) };
-        to_result(res)?;
+        self.get_result(
+            res,
+            "OH_UdmfRecord_GetPlainText",
+            &UniformDataType::PlainText,
+        )?;
         Ok(plain_text)
     }
 
     pub fn add_hyperlink(&mut self, hyperlink: &crate::uds::Hyperlink) -> Result<()> {
         // SAFETY: self.inner and hyperlink.inner are valid pointers.
         let res = unsafe { OH_UdmfRecord_AddHyperlink(self.inner, hyperlink.inner) };
-        to_result(res)
+        to_result(res, "OH_UdmfRecord_AddHyperlink")
+            .map_err(|e| e.with_type_id(&UniformDataType::Hyperlink))
     }
 
     pub fn get_hyperlink(&self) -> Result<crate::uds::Hyperlink> {
         let hyperlink = crate::uds::Hyperlink::new()?;
         // SAFETY: self.inner and hyperlink.inner are valid pointers.
         let res = unsafe { OH_UdmfRecord_GetHyperlink(self.inner, hyperlink.inner) };
-        to_result(res)?;
+        self.get_result(
+            res,
+            "OH_UdmfRecord_GetHyperlink",
+            &UniformDataType::Hyperlink,
+        )?;
         Ok(hyperlink)
     }
 
     pub fn add_html(&mut self, html: &crate::uds::Html) -> Result<()> {
         // SAFETY: self.inner and html.inner are valid pointers.
         let res = unsafe { OH_UdmfRecord_AddHtml(self.inner, html.inner) };
-        to_result(res)
+        to_result(res, "OH_UdmfRecord_AddHtml").map_err(|e| e.with_type_id(&UniformDataType::Html))
     }
 
     pub fn get_html(&self) -> Result<crate::uds::Html> {
         let html = crate::uds::Html::new()?;
         // SAFETY: self.inner and html.inner are valid pointers.
         let res = unsafe { OH_UdmfRecord_GetHtml(self.inner, html.inner) };
-        to_result(res)?;
+        self.get_result(res, "OH_UdmfRecord_GetHtml", &UniformDataType::Html)?;
         Ok(html)
     }
 
     pub fn add_app_item(&mut self, app_item: &crate::uds::AppItem) -> Result<()> {
         // SAFETY: self.inner and app_item.inner are valid pointers.
         let res = unsafe { OH_UdmfRecord_AddAppItem(self.inner, app_item.inner) };
-        to_result(res)
+        to_result(res, "OH_UdmfRecord_AddAppItem")
+            .map_err(|e| e.with_type_id(&UniformDataType::OpenHarmonyAppItem))
     }
 
     pub fn get_app_item(&self) -> Result<crate::uds::AppItem> {
         let app_item = crate::uds::AppItem::new()?;
         // SAFETY: self.inner and app_item.inner are valid pointers.
         let res = unsafe { OH_UdmfRecord_GetAppItem(self.inner, app_item.inner) };
-        to_result(res)?;
+        self.get_result(
+            res,
+            "OH_UdmfRecord_GetAppItem",
+            &UniformDataType::OpenHarmonyAppItem,
+        )?;
         Ok(app_item)
     }
 
     pub fn add_file_uri(&mut self, file_uri: &crate::uds::FileUri) -> Result<()> {
         // SAFETY: self.inner and file_uri.inner are valid pointers.
         let res = unsafe { OH_UdmfRecord_AddFileUri(self.inner, file_uri.inner) };
-        to_result(res)
+        to_result(res, "OH_UdmfRecord_AddFileUri")
+            .map_err(|e| e.with_type_id(&UniformDataType::GeneralFileUri))
     }
 
     pub fn get_file_uri(&self) -> Result<crate::uds::FileUri> {
         let file_uri = crate::uds::FileUri::new()?;
         // SAFETY: self.inner and file_uri.inner are valid pointers.
         let res = unsafe { OH_UdmfRecord_GetFileUri(self.inner, file_uri.inner) };
-        to_result(res)?;
+        self.get_result(
+            res,
+            "OH_UdmfRecord_GetFileUri",
+            &UniformDataType::GeneralFileUri,
+        )?;
         Ok(file_uri)
     }
 
     pub fn add_pixel_map(&mut self, pixel_map: &crate::uds::PixelMap) -> Result<()> {
         // SAFETY: self.inner and pixel_map.inner are valid pointers.
         let res = unsafe { OH_UdmfRecord_AddPixelMap(self.inner, pixel_map.inner) };
-        to_result(res)
+        to_result(res, "OH_UdmfRecord_AddPixelMap")
+            .map_err(|e| e.with_type_id(&UniformDataType::OpenHarmonyPixelMap))
     }
 
     pub fn get_pixel_map(&self) -> Result<crate::uds::PixelMap> {
-        let pixel_map = crate::uds::PixelMap::new()?;
+        let mut pixel_map = crate::uds::PixelMap::new()?;
         // SAFETY: self.inner and pixel_map.inner are valid pointers.
         let res = unsafe { OH_UdmfRecord_GetPixelMap(self.inner, pixel_map.inner) };
-        to_result(res)?;
+        self.get_result(
+            res,
+            "OH_UdmfRecord_GetPixelMap",
+            &UniformDataType::OpenHarmonyPixelMap,
+        )?;
+        pixel_map.has_pixel_map = true;
         Ok(pixel_map)
     }
 
@@ -273,7 +499,7 @@ impl UnifiedRecord {
         let res = unsafe {
             OH_UdmfRecord_AddArrayBuffer(self.inner, type_id.to_cstr().as_ptr(), buffer.inner)
         };
-        to_result(res)
+        to_result(res, "OH_UdmfRecord_AddArrayBuffer").map_err(|e| e.with_type_id(type_id))
     }
 
     pub fn get_array_buffer(&self, type_id: &UniformDataType) -> Result<crate::uds::ArrayBuffer> {
@@ -282,21 +508,26 @@ impl UnifiedRecord {
         let res = unsafe {
             OH_UdmfRecord_GetArrayBuffer(self.inner, type_id.to_cstr().as_ptr(), buffer.inner)
         };
-        to_result(res)?;
+        self.get_result(res, "OH_UdmfRecord_GetArrayBuffer", type_id)?;
         Ok(buffer)
     }
 
     pub fn add_content_form(&mut self, content_form: &crate::uds::ContentForm) -> Result<()> {
         // SAFETY: self.inner and content_form.inner are valid pointers.
         let res = unsafe { OH_UdmfRecord_AddContentForm(self.inner, content_form.inner) };
-        to_result(res)
+        to_result(res, "OH_UdmfRecord_AddContentForm")
+            .map_err(|e| e.with_type_id(&UniformDataType::GeneralContentForm))
     }
 
     pub fn get_content_form(&self) -> Result<crate::uds::ContentForm> {
         let content_form = crate::uds::ContentForm::new()?;
         // SAFETY: self.inner and content_form.inner are valid pointers.
         let res = unsafe { OH_UdmfRecord_GetContentForm(self.inner, content_form.inner) };
-        to_result(res)?;
+        self.get_result(
+            res,
+            "OH_UdmfRecord_GetContentForm",
+            &UniformDataType::GeneralContentForm,
+        )?;
         Ok(content_form)
     }
 }
@@ -309,3 +540,33 @@ impl Drop for UnifiedRecord {
         }
     }
 }
+
+/// A record borrowed from a [`UnifiedData`].
+///
+/// The record is owned by the `UnifiedData` it was obtained from and can not outlive it.
+pub struct RecordRef<'a> {
+    record: UnifiedRecord,
+    _data: PhantomData<&'a UnifiedData>,
+}
+
+impl RecordRef<'_> {
+    /// # Safety
+    ///
+    /// The caller must ensure that `inner` is a valid pointer to an `OH_UdmfRecord` that
+    /// stays alive for the chosen lifetime.
+    pub(crate) unsafe fn from_ptr(inner: *mut OH_UdmfRecord) -> Self {
+        Self {
+            // SAFETY: inner is valid as per the function's safety requirements. The record is not owned.
+            record: unsafe { UnifiedRecord::from_ptr(inner, false) },
+            _data: PhantomData,
+        }
+    }
+}
+
+impl Deref for RecordRef<'_> {
+    type Target = UnifiedRecord;
+
+    fn deref(&self) -> &UnifiedRecord {
+        &self.record
+    }
+}
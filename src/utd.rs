@@ -1,5 +1,7 @@
 use crate::error::{Result, UdmfError};
 use crate::types::UniformDataType;
+use std::cmp::Ordering;
+use std::collections::{HashSet, VecDeque};
 use std::ffi::{CStr, CString};
 use udmf_sys::type_descriptor::*;
 
@@ -15,7 +17,7 @@ impl TypeDescriptor {
         let inner = unsafe { OH_Utd_Create(type_id.to_cstr().as_ptr()) };
 
         if inner.is_null() {
-            return Err(UdmfError::InternalError(0));
+            return Err(UdmfError::null_pointer("OH_Utd_Create"));
         }
         Ok(Self { inner, owned: true })
     }
@@ -31,10 +33,10 @@ impl TypeDescriptor {
         unsafe { CStr::from_ptr(c_str) }.into()
     }
 
-    pub fn get_description(&self) -> String {
+    /// Get the description of this type, or `None` if the platform provides none.
+    pub fn get_description(&self) -> Option<String> {
         self.get_description_cstr()
             .map(|c| c.to_string_lossy().into_owned())
-            .unwrap_or_default()
     }
 
     /// Get the description as a CStr.
@@ -49,10 +51,10 @@ impl TypeDescriptor {
         }
     }
 
-    pub fn get_reference_url(&self) -> String {
+    /// Get the reference URL of this type, or `None` if the platform provides none.
+    pub fn get_reference_url(&self) -> Option<String> {
         self.get_reference_url_cstr()
             .map(|c| c.to_string_lossy().into_owned())
-            .unwrap_or_default()
     }
 
     /// Get the reference URL as a CStr.
@@ -67,6 +69,24 @@ impl TypeDescriptor {
         }
     }
 
+    /// Get the path of the icon file of this type, or `None` if the platform provides none.
+    pub fn get_icon_file(&self) -> Option<String> {
+        self.get_icon_file_cstr()
+            .map(|c| c.to_string_lossy().into_owned())
+    }
+
+    /// Get the icon file as a CStr.
+    pub fn get_icon_file_cstr(&self) -> Option<&CStr> {
+        // SAFETY: self.inner is a valid pointer to OH_Utd.
+        let c_ptr = unsafe { OH_Utd_GetIconFile(self.inner) };
+        if c_ptr.is_null() {
+            None
+        } else {
+            // SAFETY: c_ptr is a valid C string returned by UTD and is valid for the lifetime of self.
+            Some(unsafe { CStr::from_ptr(c_ptr) })
+        }
+    }
+
     pub fn get_mime_types(&self) -> impl Iterator<Item = String> + '_ {
         self.get_mime_types_cstr()
             .map(|c| c.to_string_lossy().into_owned())
@@ -124,12 +144,229 @@ impl TypeDescriptor {
         OwnedUtdListIter::new(list_ptr, count).map(|s| UniformDataType::from(s.as_c_str()))
     }
 
+    /// Get the types with the filename extension `extension` that belong to `belongs_to`.
+    ///
+    /// This corresponds to `getUniformDataTypesByFilenameExtension` with a `belongsTo`
+    /// argument in ArkTS.
+    pub fn types_for_extension_in(
+        extension: &str,
+        belongs_to: &UniformDataType,
+    ) -> impl Iterator<Item = UniformDataType> {
+        let belongs_to = belongs_to.clone();
+        Self::get_types_by_filename_extension(extension)
+            .filter(move |type_id| type_id == &belongs_to || type_id.is_lower_level(&belongs_to))
+    }
+
+    /// Get the types with the MIME type `mime_type` that belong to `belongs_to`.
+    pub fn types_for_mime_type_in(
+        mime_type: &str,
+        belongs_to: &UniformDataType,
+    ) -> impl Iterator<Item = UniformDataType> {
+        let belongs_to = belongs_to.clone();
+        Self::get_types_by_mime_type(mime_type)
+            .filter(move |type_id| type_id == &belongs_to || type_id.is_lower_level(&belongs_to))
+    }
+
+    /// Get the most specific type with the filename extension `extension`, optionally
+    /// restricted to the types belonging to `belongs_to`.
+    ///
+    /// See [`most_specific`](Self::most_specific) for how a single type is picked.
+    pub fn most_specific_type_for_extension(
+        extension: &str,
+        belongs_to: Option<&UniformDataType>,
+    ) -> Option<UniformDataType> {
+        match belongs_to {
+            Some(belongs_to) => {
+                Self::most_specific(Self::types_for_extension_in(extension, belongs_to))
+            }
+            None => Self::most_specific(Self::get_types_by_filename_extension(extension)),
+        }
+    }
+
+    /// Get the most specific type with the MIME type `mime_type`, optionally restricted to the
+    /// types belonging to `belongs_to`.
+    ///
+    /// See [`most_specific`](Self::most_specific) for how a single type is picked.
+    pub fn most_specific_type_for_mime_type(
+        mime_type: &str,
+        belongs_to: Option<&UniformDataType>,
+    ) -> Option<UniformDataType> {
+        match belongs_to {
+            Some(belongs_to) => {
+                Self::most_specific(Self::types_for_mime_type_in(mime_type, belongs_to))
+            }
+            None => Self::most_specific(Self::get_types_by_mime_type(mime_type)),
+        }
+    }
+
+    /// Pick the most specific of `candidates`, i.e. the first one that no other candidate
+    /// belongs to.
+    ///
+    /// If several unrelated candidates remain, e.g. two siblings, the first of them is
+    /// returned. Use a `belongs_to` filter to disambiguate such cases.
+    pub fn most_specific(
+        candidates: impl IntoIterator<Item = UniformDataType>,
+    ) -> Option<UniformDataType> {
+        let candidates: Vec<_> = candidates.into_iter().collect();
+        candidates
+            .iter()
+            .find(|candidate| {
+                !candidates
+                    .iter()
+                    .any(|other| other.is_lower_level(candidate))
+            })
+            .cloned()
+    }
+
     pub fn belongs_to(&self, other_type_id: impl Into<UniformDataType>) -> bool {
         let u_other = other_type_id.into();
         let u_self = self.get_type_id();
         // SAFETY: both u_self.to_cstr() and u_other.to_cstr() return valid C strings.
         unsafe { OH_Utd_BelongsTo(u_self.to_cstr().as_ptr(), u_other.to_cstr().as_ptr()) }
     }
+
+    /// Check whether this type is a strict descendant of `other_type_id`.
+    pub fn is_lower_level(&self, other_type_id: impl Into<UniformDataType>) -> bool {
+        self.get_type_id().is_lower_level(&other_type_id.into())
+    }
+
+    /// Check whether this type is a strict ancestor of `other_type_id`.
+    pub fn is_higher_level(&self, other_type_id: impl Into<UniformDataType>) -> bool {
+        self.get_type_id().is_higher_level(&other_type_id.into())
+    }
+
+    /// Iterate over all ancestors of this type, breadth-first and without duplicates.
+    ///
+    /// The direct parents come first, followed by their parents and so on up to the root
+    /// types. The type itself is not included.
+    pub fn ancestors(&self) -> Ancestors {
+        let type_id = self.get_type_id();
+        let mut visited = HashSet::from([type_id]);
+        let queue = self
+            .get_belonging_to_types()
+            .filter(|parent| visited.insert(parent.clone()))
+            .collect();
+        Ancestors { queue, visited }
+    }
+
+    /// Get the known types that are strict descendants of this type.
+    ///
+    /// Only the types in [`UniformDataType::all`] are considered, since the platform cannot
+    /// list the types that belong to a type.
+    pub fn descendants(&self) -> impl Iterator<Item = &'static UniformDataType> {
+        let type_id = self.get_type_id();
+        UniformDataType::all().filter(move |candidate| candidate.is_lower_level(&type_id))
+    }
+
+    /// Find the most specific type that both `a` and `b` belong to.
+    ///
+    /// If `a` and `b` share several unrelated ancestors, the first one in breadth-first order
+    /// from `a` is returned. Returns `None` if the types have no common ancestor or `a` is
+    /// unknown to the platform.
+    pub fn lowest_common_ancestor(
+        a: &UniformDataType,
+        b: &UniformDataType,
+    ) -> Option<UniformDataType> {
+        let utd = TypeDescriptor::new(a).ok()?;
+        let common: Vec<_> = std::iter::once(a.clone())
+            .chain(utd.ancestors())
+            .filter(|candidate| b == candidate || b.is_lower_level(candidate))
+            .collect();
+        common
+            .iter()
+            .find(|candidate| !common.iter().any(|other| other.is_lower_level(candidate)))
+            .cloned()
+    }
+
+    /// Copy all data of this descriptor into an owned [`TypeInfo`].
+    pub fn snapshot(&self) -> TypeInfo {
+        TypeInfo {
+            type_id: self.get_type_id(),
+            description: self.get_description(),
+            reference_url: self.get_reference_url(),
+            icon_file: self.get_icon_file(),
+            mime_types: self.get_mime_types().collect(),
+            filename_extensions: self.get_filename_extensions().collect(),
+            belonging_to_types: self.get_belonging_to_types().collect(),
+        }
+    }
+
+    /// Check whether both descriptors describe the same type.
+    pub fn equals(&self, other: &TypeDescriptor) -> bool {
+        // SAFETY: both inner pointers are valid pointers to OH_Utd.
+        unsafe { OH_Utd_Equals(self.inner, other.inner) }
+    }
+}
+
+impl PartialEq for TypeDescriptor {
+    fn eq(&self, other: &Self) -> bool {
+        self.equals(other)
+    }
+}
+
+/// Orders descriptors by the type hierarchy: a type is less than its ancestors. Types that are
+/// not related, e.g. siblings, are not comparable.
+impl PartialOrd for TypeDescriptor {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self.equals(other) {
+            Some(Ordering::Equal)
+        } else {
+            self.get_type_id().hierarchy_cmp(&other.get_type_id())
+        }
+    }
+}
+
+impl UniformDataType {
+    /// Check whether this type is a strict descendant of `other`, e.g. `general.jpeg` of
+    /// `general.image`.
+    ///
+    /// Unlike [`belongs_to`](Self::belongs_to), this asks the platform type descriptor
+    /// service and therefore also knows custom types.
+    pub fn is_lower_level(&self, other: &UniformDataType) -> bool {
+        // SAFETY: both to_cstr() calls return valid C strings.
+        unsafe { OH_Utd_IsLower(self.to_cstr().as_ptr(), other.to_cstr().as_ptr()) }
+    }
+
+    /// Check whether this type is a strict ancestor of `other`, e.g. `general.image` of
+    /// `general.jpeg`.
+    pub fn is_higher_level(&self, other: &UniformDataType) -> bool {
+        // SAFETY: both to_cstr() calls return valid C strings.
+        unsafe { OH_Utd_IsHigher(self.to_cstr().as_ptr(), other.to_cstr().as_ptr()) }
+    }
+
+    /// Compare two types by the hierarchy known to the platform.
+    ///
+    /// Returns `Less` if this type is a descendant of `other`, `Greater` if it is an ancestor,
+    /// `Equal` if both are the same type and `None` if they are unrelated, e.g. siblings.
+    pub fn hierarchy_cmp(&self, other: &UniformDataType) -> Option<Ordering> {
+        if self == other {
+            Some(Ordering::Equal)
+        } else if self.is_lower_level(other) {
+            Some(Ordering::Less)
+        } else if self.is_higher_level(other) {
+            Some(Ordering::Greater)
+        } else {
+            None
+        }
+    }
+}
+
+/// Owned copy of the data of a [`TypeDescriptor`], see [`TypeDescriptor::snapshot`].
+///
+/// Unlike the descriptor, it is `Send` and `Sync` and can be passed between threads.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TypeInfo {
+    pub type_id: UniformDataType,
+    /// `None` if the platform returned no description.
+    pub description: Option<String>,
+    /// `None` if the platform returned no reference URL.
+    pub reference_url: Option<String>,
+    /// The path of the icon file, `None` if the platform returned none.
+    pub icon_file: Option<String>,
+    pub mime_types: Vec<String>,
+    pub filename_extensions: Vec<String>,
+    pub belonging_to_types: Vec<UniformDataType>,
 }
 
 impl Drop for TypeDescriptor {
@@ -141,6 +378,29 @@ impl Drop for TypeDescriptor {
     }
 }
 
+/// Iterator over the ancestors of a type, see [`TypeDescriptor::ancestors`].
+pub struct Ancestors {
+    queue: VecDeque<UniformDataType>,
+    visited: HashSet<UniformDataType>,
+}
+
+impl Iterator for Ancestors {
+    type Item = UniformDataType;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let type_id = self.queue.pop_front()?;
+        // Types unknown to the platform have no descriptor and are treated as roots.
+        if let Ok(utd) = TypeDescriptor::new(&type_id) {
+            for parent in utd.get_belonging_to_types() {
+                if self.visited.insert(parent.clone()) {
+                    self.queue.push_back(parent);
+                }
+            }
+        }
+        Some(type_id)
+    }
+}
+
 /// An iterator over a list of strings provided by the UDMF framework.
 struct UtdListIter<'a> {
     list: &'a [*const std::os::raw::c_char],
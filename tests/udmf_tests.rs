@@ -1,6 +1,7 @@
 use std::ffi::CString;
 use udmf::{
-    AppItem, ArrayBuffer, ContentForm, FileUri, Html, Hyperlink, PixelMap, PlainText, UnifiedData,
+    AppItem, ArrayBuffer, ContentForm, DataKey, Entry, FileUri, Html, Hyperlink, NativePixelMap,
+    PixelFormat, PixelMap, PlainText, ShareOption, TypeCategory, UdmfError, UdsObject, UnifiedData,
     UnifiedRecord, UniformDataType,
 };
 
@@ -47,8 +48,22 @@ fn test_app_item() {
     app.set_ability_name("EntryAbility")
         .expect("Failed to set ability name");
 
+    app.set_id("app-id").expect("Failed to set id");
+    app.set_name("Example").expect("Failed to set name");
+    app.set_icon_id("icon-id").expect("Failed to set icon id");
+    app.set_label_id("label-id")
+        .expect("Failed to set label id");
+
     assert_eq!(app.get_bundle_name(), "com.example.app");
     assert_eq!(app.get_ability_name(), "EntryAbility");
+    assert_eq!(app.get_id(), "app-id");
+    assert_eq!(app.get_name(), "Example");
+    assert_eq!(app.get_icon_id(), "icon-id");
+    assert_eq!(app.get_label_id(), "label-id");
+
+    let restored = AppItem::try_from(&app.snapshot()).expect("Failed to restore AppItem");
+    assert_eq!(restored.get_label_id_cstr(), Some(c"label-id"));
+    assert_eq!(restored.snapshot(), app.snapshot());
 }
 
 #[test]
@@ -100,26 +115,142 @@ fn test_content_form() {
     assert_eq!(form.get_app_icon().expect("Failed to get icon"), icon);
 }
 
-// It's a bit unclear how we would get a valid PixelMap pointer to test this.
 #[test]
 fn test_pixel_map_get_set() {
     let mut pm = PixelMap::new().expect("Failed to create PixelMap");
+    assert!(
+        pm.get_pixel_map()
+            .expect("Failed to get pixel map")
+            .is_none()
+    );
+    let Err(err) = pm.to_rgba() else {
+        panic!("Expected an error for an unset pixel map");
+    };
+    assert!(matches!(err, UdmfError::TypeNotFound { .. }));
+
+    let pixels: Vec<u8> = (0..2 * 3 * 4).collect();
+    let native = NativePixelMap::new(2, 3, PixelFormat::Rgba8888, &pixels)
+        .expect("Failed to create native pixel map");
+    assert!(native.is_owned());
+
+    pm.set_pixel_map(&native).expect("Failed to set pixel map");
+    drop(native);
+
+    let native = pm
+        .get_pixel_map()
+        .expect("Failed to get pixel map")
+        .expect("Pixel map is set");
+    let info = native.get_image_info().expect("Failed to get image info");
+    assert_eq!(info.width, 2);
+    assert_eq!(info.height, 3);
+    assert_eq!(info.row_stride, 8);
+    assert_eq!(info.pixel_format, PixelFormat::Rgba8888);
+    assert_eq!(native.get_width().unwrap(), 2);
+    assert_eq!(native.get_height().unwrap(), 3);
+
+    // SAFETY: the handle stays owned by `native`, which outlives the borrow.
+    let borrowed = unsafe { NativePixelMap::from_ptr(native.as_ptr()) }.unwrap();
+    assert!(!borrowed.is_owned());
+    assert_eq!(borrowed.get_row_stride().unwrap(), 8);
+}
 
-    // SAFETY: pm is valid.
-    let initial_ptr = unsafe { pm.get_pixel_map() };
-    println!("Initial PixelMap pointer: {:?}", initial_ptr);
+#[test]
+fn test_pixel_map_tiny_alpha() {
+    // A 1x1 alpha-only pixel map is a valid pixel map like any other.
+    let native = NativePixelMap::new(1, 1, PixelFormat::Alpha8, &[0x5a])
+        .expect("Failed to create native pixel map");
+    let mut pm = PixelMap::new().expect("Failed to create PixelMap");
+    pm.set_pixel_map(&native).expect("Failed to set pixel map");
 
-    // Test setting a NULL pointer
-    let null_ptr = std::ptr::null_mut();
-    println!("Setting PixelMap to NULL...");
-    unsafe { pm.set_pixel_map(null_ptr) }.expect_err("Pixelmap nullptr should fail");
+    let mut record = UnifiedRecord::new().expect("Failed to create UnifiedRecord");
+    record.add_pixel_map(&pm).expect("Failed to add pixel map");
+    let native = record
+        .get_pixel_map()
+        .expect("Failed to get pixel map")
+        .get_pixel_map()
+        .expect("Failed to get native pixel map")
+        .expect("Pixel map is set");
+    assert_eq!(native.get_pixel_format().unwrap(), PixelFormat::Alpha8);
+    assert_eq!(native.read_pixels().unwrap()[0], 0x5a);
+}
 
-    let after_null_ptr = unsafe { pm.get_pixel_map() };
-    println!("PixelMap pointer after NULL set: {:?}", after_null_ptr);
-    assert_eq!(
-        after_null_ptr, initial_ptr,
-        "Pixelmap ptr should not change after failed set call"
+#[test]
+fn test_pixel_map_rgba_roundtrip() {
+    let pixels: Vec<u8> = (0..3 * 2 * 4).collect();
+    let pm = PixelMap::from_rgba(3, 2, &pixels).expect("Failed to create PixelMap");
+
+    let mut record = UnifiedRecord::new().expect("Failed to create UnifiedRecord");
+    record.add_pixel_map(&pm).expect("Failed to add pixel map");
+    let pm = record.get_pixel_map().expect("Failed to get pixel map");
+    assert_eq!(pm.to_rgba().expect("Failed to read pixels"), (3, 2, pixels));
+
+    let err = PixelMap::from_rgba(3, 2, &[0; 4]).err();
+    assert!(matches!(err, Some(UdmfError::InvalidParam { .. })));
+}
+
+#[cfg(feature = "image")]
+#[test]
+fn test_pixel_map_image_conversion() {
+    let image = image::DynamicImage::ImageRgb8(image::RgbImage::from_fn(4, 3, |x, y| {
+        image::Rgb([x as u8, y as u8, 7])
+    }));
+    let pm = PixelMap::try_from(&image).expect("Failed to convert image");
+    let converted = image::DynamicImage::try_from(&pm).expect("Failed to convert PixelMap");
+    assert_eq!(converted.to_rgba8(), image.to_rgba8());
+}
+
+#[test]
+fn test_uds_type_id() {
+    fn check(uds: &impl UdsObject, expected: UniformDataType) {
+        assert_eq!(uds.type_id(), expected);
+    }
+    check(&PlainText::new().unwrap(), UniformDataType::PlainText);
+    check(&Hyperlink::new().unwrap(), UniformDataType::Hyperlink);
+    check(&Html::new().unwrap(), UniformDataType::Html);
+    check(
+        &AppItem::new().unwrap(),
+        UniformDataType::OpenHarmonyAppItem,
+    );
+    check(&FileUri::new().unwrap(), UniformDataType::GeneralFileUri);
+    check(
+        &PixelMap::new().unwrap(),
+        UniformDataType::OpenHarmonyPixelMap,
+    );
+    check(
+        &ContentForm::new().unwrap(),
+        UniformDataType::GeneralContentForm,
     );
+
+    let mut html = Html::new().unwrap();
+    html.set_content("<p>Hi</p>").unwrap();
+    let mut record = UnifiedRecord::new().unwrap();
+    record.add_html(&html).unwrap();
+    assert_eq!(record.get_types(), vec![html.type_id()]);
+}
+
+#[test]
+fn test_unified_record_generic_add_get() {
+    fn roundtrip<T: UdsObject>(uds: &T) -> T {
+        let mut record = UnifiedRecord::new().expect("Failed to create UnifiedRecord");
+        record.add(uds).expect("Failed to add entry");
+        assert_eq!(record.get_types(), vec![uds.type_id()]);
+        record.get::<T>().expect("Failed to get entry")
+    }
+
+    let mut html = Html::new().unwrap();
+    html.set_content("<b>bold</b>").unwrap();
+    assert_eq!(roundtrip(&html).get_content(), "<b>bold</b>");
+
+    let mut link = Hyperlink::new().unwrap();
+    link.set_url("https://example.com").unwrap();
+    assert_eq!(roundtrip(&link).get_url(), "https://example.com");
+
+    let mut record = UnifiedRecord::new().unwrap();
+    record.add(&html).unwrap();
+    let Err(err) = record.get::<PlainText>() else {
+        panic!("Expected an error for a missing type");
+    };
+    assert!(matches!(err, UdmfError::TypeNotFound { .. }));
 }
 
 #[test]
@@ -219,4 +350,650 @@ fn test_unified_data_auxiliary() {
 
     let records = data.get_records();
     assert_eq!(records.len(), 1);
+    assert_eq!(
+        records[0]
+            .get_general_entry(&some_type)
+            .expect("Get entry from borrowed record"),
+        vec![1]
+    );
+}
+
+#[test]
+fn test_data_key() {
+    let key = DataKey::new("udmf://DataHub/com.example.app/abc").expect("Valid key");
+    assert_eq!(key.to_string(), "udmf://DataHub/com.example.app/abc");
+    assert!(DataKey::new("udmf://\0").is_err());
+}
+
+#[cfg(feature = "api-20")]
+#[test]
+fn test_store_roundtrip() {
+    use udmf::{Intention, store};
+
+    let mut data = UnifiedData::new().expect("New UnifiedData");
+    let mut record = UnifiedRecord::new().expect("New Record");
+    let mut pt = PlainText::new().expect("New PlainText");
+    pt.set_content("stored").expect("Set content");
+    record.add_plain_text(&pt).expect("Add PlainText");
+    data.add_record(&record).expect("Add record");
+    let mut properties = data.get_properties().expect("Get properties");
+    properties.set_tag("clipboard").expect("Set tag");
+    properties
+        .set_share_option(ShareOption::InApp)
+        .expect("Set share option");
+
+    let key = store::set_unified_data(Intention::DataHub, &data).expect("Set data");
+    let got = store::get_unified_data(&key, Intention::DataHub).expect("Get data");
+    assert!(got.has_type(&UniformDataType::PlainText));
+
+    let mut options = store::Options::new().expect("New Options");
+    options.set_key(&key).expect("Set key");
+    options
+        .set_intention(Intention::DataHub)
+        .expect("Set intention");
+    let found = store::get_unified_data_with_options(&options).expect("Get with options");
+    assert_eq!(found.len(), 1);
+    let text = found[0].get_primary_plain_text().expect("Get PlainText");
+    assert_eq!(text.get_content(), "stored");
+    let properties = found[0].get_properties().expect("Get properties");
+    assert_eq!(properties.get_tag(), "clipboard");
+    assert_eq!(properties.get_share_option(), Some(ShareOption::InApp));
+
+    let deleted = store::delete_unified_data(&options).expect("Delete");
+    assert_eq!(deleted.len(), 1);
+    assert!(deleted[0].has_type(&UniformDataType::PlainText));
+    let properties = deleted[0].get_properties().expect("Get properties");
+    assert_eq!(properties.get_tag(), "clipboard");
+    assert!(
+        store::get_unified_data_with_options(&options)
+            .expect("Get after delete")
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_unified_data_properties() {
+    let data = UnifiedData::new().expect("New UnifiedData");
+    let mut props = data.get_properties().expect("Get properties");
+    props.set_tag("drag-source").expect("Set tag");
+    props
+        .set_share_option(ShareOption::InApp)
+        .expect("Set share option");
+    props.set_extras_int("count", 3).expect("Set int extra");
+    props
+        .set_extras_string("origin", "gallery")
+        .expect("Set string extra");
+
+    let props = data.get_properties().expect("Get properties again");
+    assert_eq!(props.get_tag(), "drag-source");
+    assert_eq!(props.get_share_option(), Some(ShareOption::InApp));
+    assert_eq!(props.get_extras_int("count", 0), 3);
+    assert_eq!(props.get_extras_int("missing", -1), -1);
+    assert_eq!(
+        props.get_extras_string("origin").as_deref(),
+        Some("gallery")
+    );
+}
+
+#[test]
+fn test_unified_record_provider() {
+    let mut record = UnifiedRecord::new().expect("New Record");
+    record
+        .set_provider(&[UniformDataType::PlainText], |type_id| {
+            if *type_id != UniformDataType::PlainText {
+                return None;
+            }
+            let mut pt = PlainText::new().ok()?;
+            pt.set_content("provided").ok()?;
+            Some(Entry::PlainText(pt))
+        })
+        .expect("Set provider");
+
+    assert!(record.get_types().contains(&UniformDataType::PlainText));
+    assert_eq!(
+        record.get_plain_text().expect("get pt").get_content(),
+        "provided"
+    );
+}
+
+#[cfg(all(feature = "api-15", feature = "mock"))]
+#[test]
+fn test_get_data_request() {
+    use std::future::Future;
+    use std::sync::{Arc, Mutex};
+    use std::task::{Context, Poll, Waker};
+    use udmf::get_data::{
+        FileConflictOptions, GetDataFuture, GetDataRequest, ListenerStatus, ProgressIndicator,
+    };
+
+    fn poll(future: &mut GetDataFuture) -> Poll<udmf::Result<UnifiedData>> {
+        let mut cx = Context::from_waker(Waker::noop());
+        std::pin::Pin::new(future).poll(&mut cx)
+    }
+
+    fn failure(poll: Poll<udmf::Result<UnifiedData>>) -> ListenerStatus {
+        match poll {
+            Poll::Ready(Err(UdmfError::RetrievalFailed(status))) => status,
+            _ => panic!("Expected a failed retrieval"),
+        }
+    }
+
+    // The progress listener is process-wide, so all retrievals are checked in one test.
+    let updates = Arc::new(Mutex::new(Vec::new()));
+    let recorded = Arc::clone(&updates);
+    let request = GetDataRequest::new()
+        .dest_uri("file://docs/storage/Users/currentUser/Download")
+        .expect("Set dest uri")
+        .file_conflict_options(FileConflictOptions::Skip)
+        .progress_indicator(ProgressIndicator::Default)
+        .on_progress(move |info, data| {
+            recorded
+                .lock()
+                .unwrap()
+                .push((info.progress, info.status, data.is_some()))
+        });
+    let (params, mut future) = request.build_future().expect("Build params");
+    assert!(poll(&mut future).is_pending());
+
+    let mut text = PlainText::new().unwrap();
+    text.set_content("dropped").unwrap();
+    let mut record = UnifiedRecord::new().unwrap();
+    record.add_plain_text(&text).unwrap();
+    let mut data = UnifiedData::new().unwrap();
+    data.add_record(&record).unwrap();
+
+    params.mock_progress(50, ListenerStatus::Processing, None);
+    assert!(poll(&mut future).is_pending());
+    params.mock_progress(100, ListenerStatus::Finished, Some(&data));
+    drop(data);
+    let Poll::Ready(Ok(retrieved)) = poll(&mut future) else {
+        panic!("Expected the retrieved data");
+    };
+    let records = retrieved.get_records();
+    assert_eq!(records.len(), 1);
+    assert_eq!(
+        records[0].get_plain_text().unwrap().get_content(),
+        "dropped"
+    );
+    assert_eq!(
+        *updates.lock().unwrap(),
+        vec![
+            (50, ListenerStatus::Processing, false),
+            (100, ListenerStatus::Finished, true),
+        ]
+    );
+    // Updates after the terminal one are ignored.
+    params.mock_progress(100, ListenerStatus::Finished, None);
+    assert_eq!(updates.lock().unwrap().len(), 2);
+    drop(params);
+    assert_eq!(updates.lock().unwrap().len(), 2);
+
+    let (params, mut future) = GetDataRequest::new().build_future().unwrap();
+    params.mock_progress(10, ListenerStatus::SyncFailed, None);
+    assert_eq!(failure(poll(&mut future)), ListenerStatus::SyncFailed);
+    drop(params);
+
+    // Building a new request cancels the previous one.
+    let (first_params, mut first) = GetDataRequest::new().build_future().unwrap();
+    let (second_params, mut second) = GetDataRequest::new().build_future().unwrap();
+    assert_eq!(failure(poll(&mut first)), ListenerStatus::Canceled);
+    assert!(poll(&mut second).is_pending());
+
+    // Dropping the parameters cancels their retrieval and uninstalls the listener.
+    drop(second_params);
+    assert_eq!(failure(poll(&mut second)), ListenerStatus::Canceled);
+    drop(first_params);
+}
+
+#[test]
+fn test_unified_data_record_access() {
+    let mut data = UnifiedData::new().expect("New UnifiedData");
+    assert_eq!(data.get_record_count(), 0);
+    assert!(data.get_record(0).is_none());
+
+    let mut first = UnifiedRecord::new().expect("New Record");
+    let mut html = Html::new().expect("New Html");
+    html.set_content("<b>bold</b>").expect("Set html");
+    first.add_html(&html).expect("Add Html");
+    data.add_record(&first).expect("Add record");
+
+    let mut second = UnifiedRecord::new().expect("New Record");
+    let mut pt = PlainText::new().expect("New PlainText");
+    pt.set_content("plain").expect("Set content");
+    second.add_plain_text(&pt).expect("Add PlainText");
+    data.add_record(&second).expect("Add record");
+
+    assert_eq!(data.get_record_count(), 2);
+    let record = data.get_record(1).expect("Second record");
+    assert!(record.get_types().contains(&UniformDataType::PlainText));
+    assert!(data.get_record(2).is_none());
+
+    assert_eq!(
+        data.get_primary_plain_text()
+            .expect("Primary plain text")
+            .get_content(),
+        "plain"
+    );
+    assert_eq!(
+        data.get_primary_html().expect("Primary html").get_content(),
+        "<b>bold</b>"
+    );
+    assert!(data.is_local());
+}
+
+#[test]
+fn test_unified_record_entries() {
+    let mut record = UnifiedRecord::new().expect("New Record");
+
+    let mut pt = PlainText::new().expect("New PlainText");
+    pt.set_content("text").expect("Set content");
+    record
+        .add_entry(&Entry::PlainText(pt))
+        .expect("Add PlainText entry");
+
+    let custom_type = UniformDataType::Other(CString::new("custom.entry").unwrap());
+    record
+        .add_entry(&Entry::General(custom_type.clone(), vec![4, 2]))
+        .expect("Add general entry");
+
+    let mut seen = 0;
+    for result in record.entries() {
+        let (type_id, entry) = result.expect("Read entry");
+        match entry {
+            Entry::PlainText(pt) => {
+                assert_eq!(type_id, UniformDataType::PlainText);
+                assert_eq!(pt.get_content(), "text");
+            }
+            Entry::General(general_type, data) => {
+                assert_eq!(general_type, custom_type);
+                assert_eq!(data, vec![4, 2]);
+            }
+            _ => panic!("Unexpected entry of type {type_id}"),
+        }
+        seen += 1;
+    }
+    assert_eq!(seen, 2);
+}
+
+#[test]
+fn test_missing_type_error() {
+    let record = UnifiedRecord::new().expect("New Record");
+    let Err(err) = record.get_html() else {
+        panic!("Record has no html");
+    };
+    let UdmfError::TypeNotFound { context } = &err else {
+        panic!("Unexpected error {err:?}");
+    };
+    assert_eq!(context.operation, "OH_UdmfRecord_GetHtml");
+    assert_eq!(context.type_id, Some(UniformDataType::Html));
+    assert_eq!(
+        UdmfError::from_code(err.code().0, "OH_UdmfRecord_GetHtml")
+            .expect("Error code")
+            .code(),
+        err.code()
+    );
+    assert!(UdmfError::from_code(0, "OH_UdmfRecord_GetHtml").is_none());
+}
+
+#[test]
+fn test_uds_snapshots() {
+    use udmf::snapshot::{ArrayBufferSnapshot, ContentFormSnapshot, HtmlSnapshot};
+
+    let mut html = Html::new().expect("New Html");
+    html.set_content("<b>bold</b>").expect("Set content");
+    html.set_plain_content("bold").expect("Set plain content");
+    let snapshot = html.snapshot();
+    assert_eq!(
+        snapshot,
+        HtmlSnapshot {
+            content: "<b>bold</b>".to_string(),
+            plain_content: "bold".to_string(),
+        }
+    );
+    let restored = Html::try_from(&snapshot).expect("Restore Html");
+    assert_eq!(restored.snapshot(), snapshot);
+
+    let form = ContentFormSnapshot {
+        title: "Title".to_string(),
+        thumb_data: vec![1, 2, 3],
+        ..Default::default()
+    };
+    let restored = ContentForm::try_from(&form).expect("Restore ContentForm");
+    assert_eq!(restored.snapshot().expect("Snapshot ContentForm"), form);
+
+    let buffer = ArrayBufferSnapshot { data: vec![9, 8] };
+    let restored = ArrayBuffer::try_from(&buffer).expect("Restore ArrayBuffer");
+    assert_eq!(restored.snapshot().expect("Snapshot ArrayBuffer"), buffer);
+    let empty = ArrayBufferSnapshot::default();
+    let restored = ArrayBuffer::try_from(&empty).expect("Restore empty ArrayBuffer");
+    assert_eq!(
+        restored.snapshot().expect("Snapshot empty ArrayBuffer"),
+        empty
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde() {
+    use udmf::snapshot::PlainTextSnapshot;
+
+    let json = serde_json::to_string(&UniformDataType::PlainText).expect("Serialize type");
+    assert_eq!(json, r#""general.plain-text""#);
+    let custom: UniformDataType =
+        serde_json::from_str(r#""com.example.custom""#).expect("Deserialize type");
+    assert_eq!(
+        custom,
+        UniformDataType::Other(CString::new("com.example.custom").unwrap())
+    );
+    assert!(serde_json::from_str::<UniformDataType>(r#""nul\u0000""#).is_err());
+
+    let snapshot = PlainTextSnapshot {
+        content: "text".to_string(),
+        abstract_text: "abstract".to_string(),
+    };
+    let json = serde_json::to_string(&snapshot).expect("Serialize snapshot");
+    assert_eq!(
+        serde_json::from_str::<PlainTextSnapshot>(&json).expect("Deserialize snapshot"),
+        snapshot
+    );
+}
+
+#[test]
+fn test_uniform_data_type_string_roundtrip() {
+    let known = [
+        UniformDataType::Entity,
+        UniformDataType::Object,
+        UniformDataType::CompositeObject,
+        UniformDataType::Text,
+        UniformDataType::PlainText,
+        UniformDataType::Html,
+        UniformDataType::Hyperlink,
+        UniformDataType::Xml,
+        UniformDataType::SourceCode,
+        UniformDataType::Script,
+        UniformDataType::ShellScript,
+        UniformDataType::CshScript,
+        UniformDataType::PerlScript,
+        UniformDataType::PhpScript,
+        UniformDataType::PythonScript,
+        UniformDataType::RubyScript,
+        UniformDataType::TypeScript,
+        UniformDataType::JavaScript,
+        UniformDataType::CHeader,
+        UniformDataType::CSource,
+        UniformDataType::CPlusPlusHeader,
+        UniformDataType::CPlusPlusSource,
+        UniformDataType::JavaSource,
+        UniformDataType::Ebook,
+        UniformDataType::Epub,
+        UniformDataType::Azw,
+        UniformDataType::Azw3,
+        UniformDataType::Kfx,
+        UniformDataType::Mobi,
+        UniformDataType::Media,
+        UniformDataType::Image,
+        UniformDataType::Jpeg,
+        UniformDataType::Png,
+        UniformDataType::RawImage,
+        UniformDataType::Tiff,
+        UniformDataType::Bmp,
+        UniformDataType::Ico,
+        UniformDataType::PhotoshopImage,
+        UniformDataType::AiImage,
+        UniformDataType::WordDoc,
+        UniformDataType::Excel,
+        UniformDataType::Ppt,
+        UniformDataType::Pdf,
+        UniformDataType::Postscript,
+        UniformDataType::EncapsulatedPostscript,
+        UniformDataType::Video,
+        UniformDataType::Avi,
+        UniformDataType::Mpeg,
+        UniformDataType::Mpeg4,
+        UniformDataType::Video3gpp,
+        UniformDataType::Video3gpp2,
+        UniformDataType::WindowsMediaWm,
+        UniformDataType::WindowsMediaWmv,
+        UniformDataType::WindowsMediaWmp,
+        UniformDataType::Audio,
+        UniformDataType::Aac,
+        UniformDataType::Aiff,
+        UniformDataType::Alac,
+        UniformDataType::Flac,
+        UniformDataType::Mp3,
+        UniformDataType::Ogg,
+        UniformDataType::Pcm,
+        UniformDataType::WindowsMediaWma,
+        UniformDataType::WaveformAudio,
+        UniformDataType::WindowsMediaWmx,
+        UniformDataType::WindowsMediaWvx,
+        UniformDataType::WindowsMediaWax,
+        UniformDataType::GeneralFile,
+        UniformDataType::Directory,
+        UniformDataType::Folder,
+        UniformDataType::Symlink,
+        UniformDataType::Archive,
+        UniformDataType::Bz2Archive,
+        UniformDataType::DiskImage,
+        UniformDataType::TarArchive,
+        UniformDataType::ZipArchive,
+        UniformDataType::JavaArchive,
+        UniformDataType::GnuTarArchive,
+        UniformDataType::GnuZipArchive,
+        UniformDataType::GnuZipTarArchive,
+        UniformDataType::Calendar,
+        UniformDataType::Contact,
+        UniformDataType::Database,
+        UniformDataType::Message,
+        UniformDataType::Vcard,
+        UniformDataType::Navigation,
+        UniformDataType::Location,
+        UniformDataType::OpenHarmonyForm,
+        UniformDataType::OpenHarmonyAppItem,
+        UniformDataType::OpenHarmonyPixelMap,
+        UniformDataType::OpenHarmonyAtomicService,
+        UniformDataType::OpenHarmonyPackage,
+        UniformDataType::OpenHarmonyHap,
+        UniformDataType::Smil,
+        UniformDataType::Markdown,
+        UniformDataType::Fax,
+        UniformDataType::JfxFax,
+        UniformDataType::EfxFax,
+        UniformDataType::XbitmapImage,
+        UniformDataType::TgaImage,
+        UniformDataType::SgiImage,
+        UniformDataType::OpenexrImage,
+        UniformDataType::FlashpixImage,
+        UniformDataType::Realmedia,
+        UniformDataType::AuAudio,
+        UniformDataType::AifcAudio,
+        UniformDataType::Sd2Audio,
+        UniformDataType::Realaudio,
+        UniformDataType::Openxml,
+        UniformDataType::WordprocessingmlDocument,
+        UniformDataType::SpreadsheetmlSheet,
+        UniformDataType::PresentationmlPresentation,
+        UniformDataType::Opendocument,
+        UniformDataType::OpendocumentText,
+        UniformDataType::OpendocumentSpreadsheet,
+        UniformDataType::OpendocumentPresentation,
+        UniformDataType::OpendocumentGraphics,
+        UniformDataType::OpendocumentFormula,
+        UniformDataType::StuffitArchive,
+        UniformDataType::Vcs,
+        UniformDataType::Ics,
+        UniformDataType::Executable,
+        UniformDataType::PortableExecutable,
+        UniformDataType::SunJavaClass,
+        UniformDataType::Font,
+        UniformDataType::TruetypeFont,
+        UniformDataType::TruetypeCollectionFont,
+        UniformDataType::OpentypeFont,
+        UniformDataType::PostscriptFont,
+        UniformDataType::PostscriptPfbFont,
+        UniformDataType::PostscriptPfaFont,
+        UniformDataType::OpenHarmonyHdoc,
+        UniformDataType::OpenHarmonyHinote,
+        UniformDataType::OpenHarmonyStyledString,
+        UniformDataType::OpenHarmonyWant,
+        UniformDataType::GeneralFileUri,
+        UniformDataType::GeneralContentForm,
+    ];
+    for type_id in known {
+        let id = type_id.as_str().expect("Known type has a static id");
+        assert_eq!(id.as_bytes(), type_id.to_cstr().to_bytes());
+        assert_eq!(type_id.to_string(), id);
+        assert_eq!(
+            id.parse::<UniformDataType>().expect("Parse type id"),
+            type_id
+        );
+        assert_eq!(
+            UniformDataType::try_from(id).expect("Convert type id"),
+            type_id
+        );
+    }
+
+    let custom: UniformDataType = "com.example.custom".parse().expect("Parse custom id");
+    assert_eq!(
+        custom,
+        UniformDataType::Other(CString::new("com.example.custom").unwrap())
+    );
+    assert_eq!(custom.as_str(), None);
+    assert_eq!(custom.to_string(), "com.example.custom");
+    assert!(UniformDataType::try_from("general.plain\0text").is_err());
+}
+
+#[test]
+fn test_known_types() {
+    let ids: std::collections::HashSet<_> = udmf::known_types::KNOWN_TYPES
+        .iter()
+        .map(|metadata| metadata.id)
+        .collect();
+    assert_eq!(ids.len(), udmf::known_types::KNOWN_TYPES.len());
+
+    for type_id in UniformDataType::all() {
+        let metadata = type_id.metadata().expect("Known type has metadata");
+        assert_eq!(&metadata.type_id, type_id);
+        assert_eq!(Some(metadata.id), type_id.as_str());
+        assert!(metadata.api_level >= 12);
+    }
+
+    assert_eq!(UniformDataType::Png.category(), Some(TypeCategory::Image));
+    assert_eq!(
+        UniformDataType::OpenHarmonyPixelMap.category(),
+        Some(TypeCategory::OpenHarmony)
+    );
+    assert_eq!(
+        UniformDataType::GeneralContentForm
+            .metadata()
+            .map(|metadata| metadata.api_level),
+        Some(14)
+    );
+    let custom = UniformDataType::Other(CString::new("com.example.custom").unwrap());
+    assert!(custom.metadata().is_none());
+}
+
+#[test]
+fn test_unified_data_negotiate() {
+    let mut data = UnifiedData::new().expect("New Data");
+
+    let mut html = Html::new().expect("New Html");
+    html.set_content("<p>text</p>").expect("Set content");
+    let mut record = UnifiedRecord::new().expect("New Record");
+    record.add_html(&html).expect("Add html");
+    data.add_record(&record).expect("Add record");
+
+    let mut record = UnifiedRecord::new().expect("New Record");
+    record
+        .add_general_entry(&UniformDataType::Png, &[0x89, b'P', b'N', b'G'])
+        .expect("Add png");
+    data.add_record(&record).expect("Add record");
+
+    let (record, type_id) = data
+        .negotiate(&[UniformDataType::PlainText, UniformDataType::Html])
+        .expect("Exact match");
+    assert_eq!(type_id, UniformDataType::Html);
+    assert_eq!(
+        record.get_html().expect("Get html").get_content(),
+        "<p>text</p>"
+    );
+
+    // An exact match wins over a closer preference that only matches an ancestor.
+    let (_, type_id) = data
+        .negotiate(&[UniformDataType::Text, UniformDataType::Png])
+        .expect("Exact match");
+    assert_eq!(type_id, UniformDataType::Png);
+
+    let (record, type_id) = data
+        .negotiate(&[UniformDataType::Image])
+        .expect("Ancestor match");
+    assert_eq!(type_id, UniformDataType::Png);
+    assert_eq!(
+        record.get_general_entry(&type_id).expect("Get png"),
+        [0x89, b'P', b'N', b'G']
+    );
+
+    // The closest ancestor wins regardless of the preference order.
+    let (_, type_id) = data
+        .negotiate(&[UniformDataType::Object, UniformDataType::Image])
+        .expect("Ancestor match");
+    assert_eq!(type_id, UniformDataType::Png);
+
+    assert!(data.negotiate(&[UniformDataType::Audio]).is_none());
+    assert!(data.negotiate(&[]).is_none());
+}
+
+#[test]
+fn test_unified_data_negotiate_distance_over_preference() {
+    // One record with two entries, each below a different accepted type.
+    let mut record = UnifiedRecord::new().expect("New Record");
+    for type_id in [UniformDataType::JavaArchive, UniformDataType::Png] {
+        record
+            .add_general_entry(&type_id, &[0xca, 0xfe])
+            .expect("Add entry");
+    }
+    let mut data = UnifiedData::new().expect("New Data");
+    data.add_record(&record).expect("Add record");
+
+    // The png is one level below `general.image`, the archive two levels below
+    // `general.object`. The closer match wins although `general.object` is preferred.
+    let (_, type_id) = data
+        .negotiate(&[UniformDataType::Object, UniformDataType::Image])
+        .expect("Ancestor match");
+    assert_eq!(type_id, UniformDataType::Png);
+
+    // Both are direct children of an accepted type, so the preference decides.
+    let (_, type_id) = data
+        .negotiate(&[UniformDataType::Archive, UniformDataType::Image])
+        .expect("Ancestor match");
+    assert_eq!(type_id, UniformDataType::JavaArchive);
+    let (_, type_id) = data
+        .negotiate(&[UniformDataType::Image, UniformDataType::Archive])
+        .expect("Ancestor match");
+    assert_eq!(type_id, UniformDataType::Png);
+}
+
+#[test]
+fn test_unified_data_negotiate_depth() {
+    // Both entries are direct children of `general.executable`, even though it is only the
+    // second parent of the java archive. The earlier record wins the tie.
+    let mut data = UnifiedData::new().expect("New Data");
+    for type_id in [UniformDataType::JavaArchive, UniformDataType::SunJavaClass] {
+        let mut record = UnifiedRecord::new().expect("New Record");
+        record
+            .add_general_entry(&type_id, &[0xca, 0xfe])
+            .expect("Add entry");
+        data.add_record(&record).expect("Add record");
+    }
+
+    let (_, type_id) = data
+        .negotiate(&[UniformDataType::Executable])
+        .expect("Ancestor match");
+    assert_eq!(type_id, UniformDataType::JavaArchive);
+
+    // A direct parent is closer than a grandparent, whichever record provides it.
+    let (_, type_id) = data
+        .negotiate(&[UniformDataType::Archive, UniformDataType::Object])
+        .expect("Ancestor match");
+    assert_eq!(type_id, UniformDataType::JavaArchive);
 }
@@ -1,4 +1,4 @@
-use udmf::{TypeDescriptor, UniformDataType};
+use udmf::{TypeCache, TypeDescriptor, UniformDataType};
 
 #[test]
 fn test_type_descriptor_basic() {
@@ -7,7 +7,7 @@ fn test_type_descriptor_basic() {
         TypeDescriptor::new(&UniformDataType::PlainText).expect("Failed to create TypeDescriptor");
 
     assert_eq!(utd.get_type_id(), UniformDataType::PlainText);
-    assert!(!utd.get_description().is_empty());
+    assert!(utd.get_description().is_some_and(|d| !d.is_empty()));
 
     // Check relationships
     assert!(utd.belongs_to(UniformDataType::Text));
@@ -34,8 +34,12 @@ fn test_type_descriptor_url() {
     let utd =
         TypeDescriptor::new(&UniformDataType::PlainText).expect("Failed to create TypeDescriptor");
     let url = utd.get_reference_url();
-    // It might be empty for plain-text, but we test the API call doesn't crash
-    println!("Reference URL: {}", url);
+    assert_eq!(
+        url,
+        utd.get_reference_url_cstr()
+            .map(|c| c.to_string_lossy().into_owned())
+    );
+    assert_eq!(utd.snapshot().reference_url, url);
 }
 
 #[test]
@@ -46,3 +50,310 @@ fn test_type_descriptor_search() {
     let mut types = TypeDescriptor::get_types_by_mime_type("text/plain");
     assert!(types.any(|x| x == UniformDataType::PlainText));
 }
+
+#[test]
+fn test_offline_hierarchy() {
+    assert!(UniformDataType::PlainText.belongs_to(&UniformDataType::Text));
+    assert!(UniformDataType::PlainText.belongs_to(&UniformDataType::Object));
+    assert!(UniformDataType::PlainText.belongs_to(&UniformDataType::PlainText));
+    assert!(!UniformDataType::Text.belongs_to(&UniformDataType::PlainText));
+    assert!(UniformDataType::OpendocumentText.belongs_to(&UniformDataType::Archive));
+    assert!(UniformDataType::OpendocumentText.belongs_to(&UniformDataType::CompositeObject));
+
+    assert!(UniformDataType::Png.mime_types().any(|x| x == "image/png"));
+    assert!(
+        UniformDataType::Jpeg
+            .filename_extensions()
+            .any(|x| x == ".jpg")
+    );
+    assert_eq!(
+        UniformDataType::Html.belonging_to_types(),
+        &[UniformDataType::Text]
+    );
+
+    let custom = UniformDataType::Other(std::ffi::CString::new("com.example.custom").unwrap());
+    assert!(custom.belongs_to(&custom));
+    assert!(!custom.belongs_to(&UniformDataType::Object));
+    assert_eq!(custom.mime_types().count(), 0);
+
+    // Every parent is a known type and every known type is below one of the two roots.
+    for type_id in UniformDataType::all() {
+        for parent in type_id.belonging_to_types() {
+            assert!(
+                parent.metadata().is_some(),
+                "{type_id} has unknown parent {parent}"
+            );
+        }
+        assert!(
+            type_id.belongs_to(&UniformDataType::Entity)
+                || type_id.belongs_to(&UniformDataType::Object)
+        );
+    }
+}
+
+// The mock registry is built from the offline table, so on the host this would only compare
+// the table with itself. The test is meaningful on a device, against the system registry.
+#[cfg(not(feature = "mock"))]
+#[test]
+fn test_offline_hierarchy_matches_descriptor() {
+    for type_id in [
+        UniformDataType::PlainText,
+        UniformDataType::Html,
+        UniformDataType::Png,
+        UniformDataType::Pdf,
+    ] {
+        let utd = TypeDescriptor::new(&type_id).expect("Failed to create TypeDescriptor");
+        let parents: Vec<_> = utd.get_belonging_to_types().collect();
+        assert_eq!(parents, type_id.belonging_to_types());
+        let mime_types: Vec<_> = utd.get_mime_types().collect();
+        assert_eq!(mime_types, type_id.mime_types().collect::<Vec<_>>());
+        let extensions: Vec<_> = utd.get_filename_extensions().collect();
+        assert_eq!(
+            extensions,
+            type_id.filename_extensions().collect::<Vec<_>>()
+        );
+    }
+}
+
+#[test]
+fn test_type_hierarchy_comparison() {
+    use std::cmp::Ordering;
+
+    assert!(UniformDataType::Jpeg.is_lower_level(&UniformDataType::Image));
+    assert!(!UniformDataType::Jpeg.is_lower_level(&UniformDataType::Jpeg));
+    assert!(UniformDataType::Image.is_higher_level(&UniformDataType::Jpeg));
+    assert!(!UniformDataType::Jpeg.is_lower_level(&UniformDataType::Png));
+    assert_eq!(
+        UniformDataType::Jpeg.hierarchy_cmp(&UniformDataType::Media),
+        Some(Ordering::Less)
+    );
+    assert_eq!(
+        UniformDataType::Jpeg.hierarchy_cmp(&UniformDataType::Png),
+        None
+    );
+
+    let jpeg =
+        TypeDescriptor::new(&UniformDataType::Jpeg).expect("Failed to create TypeDescriptor");
+    let image =
+        TypeDescriptor::new(&UniformDataType::Image).expect("Failed to create TypeDescriptor");
+    let png = TypeDescriptor::new(&UniformDataType::Png).expect("Failed to create TypeDescriptor");
+    let jpeg2 =
+        TypeDescriptor::new(&UniformDataType::Jpeg).expect("Failed to create TypeDescriptor");
+    assert!(jpeg.is_lower_level(UniformDataType::Image));
+    assert!(image.is_higher_level(UniformDataType::Jpeg));
+    assert!(jpeg.equals(&jpeg2));
+    assert!(jpeg == jpeg2);
+    assert!(jpeg != png);
+    assert!(jpeg < image);
+    assert!(image > png);
+    assert_eq!(jpeg.partial_cmp(&png), None);
+}
+
+#[test]
+fn test_type_traversal() {
+    let jpeg =
+        TypeDescriptor::new(&UniformDataType::Jpeg).expect("Failed to create TypeDescriptor");
+    let ancestors: Vec<_> = jpeg.ancestors().collect();
+    assert_eq!(
+        ancestors,
+        [
+            UniformDataType::Image,
+            UniformDataType::Media,
+            UniformDataType::Object
+        ]
+    );
+
+    // Open document text belongs to two hierarchies that share `general.object`.
+    let odt = TypeDescriptor::new(&UniformDataType::OpendocumentText)
+        .expect("Failed to create TypeDescriptor");
+    let ancestors: Vec<_> = odt.ancestors().collect();
+    assert_eq!(
+        ancestors
+            .iter()
+            .filter(|t| **t == UniformDataType::Object)
+            .count(),
+        1
+    );
+
+    let image =
+        TypeDescriptor::new(&UniformDataType::Image).expect("Failed to create TypeDescriptor");
+    let descendants: Vec<_> = image.descendants().collect();
+    assert!(descendants.contains(&&UniformDataType::Jpeg));
+    assert!(descendants.contains(&&UniformDataType::OpenHarmonyPixelMap));
+    assert!(!descendants.contains(&&UniformDataType::Image));
+    assert!(!descendants.contains(&&UniformDataType::Mpeg));
+
+    assert_eq!(
+        TypeDescriptor::lowest_common_ancestor(&UniformDataType::Jpeg, &UniformDataType::Png),
+        Some(UniformDataType::Image)
+    );
+    assert_eq!(
+        TypeDescriptor::lowest_common_ancestor(&UniformDataType::Jpeg, &UniformDataType::Mpeg),
+        Some(UniformDataType::Media)
+    );
+    assert_eq!(
+        TypeDescriptor::lowest_common_ancestor(&UniformDataType::Image, &UniformDataType::Png),
+        Some(UniformDataType::Image)
+    );
+    assert_eq!(
+        TypeDescriptor::lowest_common_ancestor(&UniformDataType::Folder, &UniformDataType::Png),
+        None
+    );
+}
+
+#[test]
+fn test_type_cache() {
+    let cache = TypeCache::new();
+    let info = cache
+        .get(&UniformDataType::PlainText)
+        .expect("Failed to resolve type");
+    assert_eq!(info.type_id, UniformDataType::PlainText);
+    assert!(info.description.as_ref().is_some_and(|d| !d.is_empty()));
+    assert!(info.mime_types.iter().any(|x| x == "text/plain"));
+    assert!(info.filename_extensions.iter().any(|x| x == ".txt"));
+    assert_eq!(info.belonging_to_types, [UniformDataType::Text]);
+
+    let again = cache
+        .get(&UniformDataType::PlainText)
+        .expect("Failed to resolve type");
+    assert!(std::sync::Arc::ptr_eq(&info, &again));
+
+    let custom = UniformDataType::Other(std::ffi::CString::new("com.example.unknown").unwrap());
+    assert!(cache.get(&custom).is_err());
+
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            std::thread::spawn(|| {
+                TypeCache::global()
+                    .get(&UniformDataType::Html)
+                    .map(|info| info.type_id.clone())
+            })
+        })
+        .collect();
+    for handle in handles {
+        assert_eq!(
+            handle.join().unwrap().expect("Failed to resolve type"),
+            UniformDataType::Html
+        );
+    }
+}
+
+#[test]
+fn test_type_info_snapshot() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<udmf::TypeInfo>();
+
+    let utd = TypeDescriptor::new(&UniformDataType::Png).expect("Failed to create TypeDescriptor");
+    let info = utd.snapshot();
+    drop(utd);
+    assert_eq!(info.type_id, UniformDataType::Png);
+    assert!(info.filename_extensions.iter().any(|x| x == ".png"));
+
+    let moved = info.clone();
+    let from_thread = std::thread::spawn(move || moved).join().unwrap();
+    assert_eq!(from_thread, info);
+
+    #[cfg(feature = "serde")]
+    {
+        let json = serde_json::to_string(&info).expect("Serialize TypeInfo");
+        assert!(json.contains(r#""type_id":"general.png""#));
+        let restored: udmf::TypeInfo = serde_json::from_str(&json).expect("Deserialize TypeInfo");
+        assert_eq!(restored, info);
+    }
+}
+
+#[test]
+fn test_type_descriptor_icon_file() {
+    let utd =
+        TypeDescriptor::new(&UniformDataType::PlainText).expect("Failed to create TypeDescriptor");
+    // Not every type has an icon, but the call must not crash.
+    let icon_file = utd.get_icon_file();
+    assert_eq!(
+        icon_file,
+        utd.get_icon_file_cstr()
+            .map(|c| c.to_string_lossy().into_owned())
+    );
+    assert_eq!(utd.snapshot().icon_file, icon_file);
+}
+
+#[test]
+fn test_filtered_type_lookups() {
+    let types: Vec<_> =
+        TypeDescriptor::types_for_extension_in(".ts", &UniformDataType::Text).collect();
+    assert!(types.contains(&UniformDataType::TypeScript));
+    assert!(
+        TypeDescriptor::types_for_extension_in(".ts", &UniformDataType::Image)
+            .next()
+            .is_none()
+    );
+    assert_eq!(
+        TypeDescriptor::most_specific_type_for_extension(".png", None),
+        Some(UniformDataType::Png)
+    );
+
+    // `text/calendar` is listed by the calendar base type and its more specific formats.
+    let calendars: Vec<_> =
+        TypeDescriptor::types_for_mime_type_in("text/calendar", &UniformDataType::Calendar)
+            .collect();
+    assert!(calendars.contains(&UniformDataType::Calendar));
+    let most_specific = TypeDescriptor::most_specific_type_for_mime_type(
+        "text/calendar",
+        Some(&UniformDataType::Calendar),
+    )
+    .expect("Most specific calendar type");
+    assert_ne!(most_specific, UniformDataType::Calendar);
+    assert!(most_specific.is_lower_level(&UniformDataType::Calendar));
+
+    assert_eq!(
+        TypeDescriptor::most_specific([
+            UniformDataType::Image,
+            UniformDataType::Jpeg,
+            UniformDataType::Media
+        ]),
+        Some(UniformDataType::Jpeg)
+    );
+    assert_eq!(TypeDescriptor::most_specific([]), None);
+}
+
+#[test]
+fn test_lookup_across_parents() {
+    // No filename extension of the built-in table is shared by several types, but
+    // `text/calendar` is listed by types with different parents.
+    let parents: Vec<_> = TypeDescriptor::get_types_by_mime_type("text/calendar")
+        .map(|type_id| type_id.belonging_to_types())
+        .collect();
+    assert!(parents.len() > 1);
+    assert!(parents.windows(2).any(|pair| pair[0] != pair[1]));
+
+    // All of them are candidates under the common ancestor, the most specific one is not the
+    // base type.
+    let objects: Vec<_> =
+        TypeDescriptor::types_for_mime_type_in("text/calendar", &UniformDataType::Object).collect();
+    assert!(objects.contains(&UniformDataType::Calendar));
+    assert!(objects.contains(&UniformDataType::Vcs));
+    assert!(objects.contains(&UniformDataType::Ics));
+    let most_specific = TypeDescriptor::most_specific_type_for_mime_type("text/calendar", None)
+        .expect("Most specific calendar type");
+    assert!(most_specific.is_lower_level(&UniformDataType::Calendar));
+
+    // Filtering by one of the formats picks exactly that format.
+    assert_eq!(
+        TypeDescriptor::types_for_mime_type_in("text/calendar", &UniformDataType::Ics)
+            .collect::<Vec<_>>(),
+        [UniformDataType::Ics]
+    );
+    assert_eq!(
+        TypeDescriptor::most_specific_type_for_mime_type(
+            "text/calendar",
+            Some(&UniformDataType::Ics)
+        ),
+        Some(UniformDataType::Ics)
+    );
+    assert_eq!(
+        TypeDescriptor::most_specific_type_for_mime_type(
+            "text/calendar",
+            Some(&UniformDataType::Image)
+        ),
+        None
+    );
+}